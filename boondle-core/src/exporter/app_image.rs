@@ -0,0 +1,280 @@
+/*
+* Copyright (c) 2025 luxreduxdelux
+*
+* Redistribution and use in source and binary forms, with or without
+* modification, are permitted provided that the following conditions are met:
+*
+* 1. Redistributions of source code must retain the above copyright notice,
+* this list of conditions and the following disclaimer.
+*
+* 2. Redistributions in binary form must reproduce the above copyright notice,
+* this list of conditions and the following disclaimer in the documentation
+* and/or other materials provided with the distribution.
+*
+* Subject to the terms and conditions of this license, each copyright holder
+* and contributor hereby grants to those receiving rights under this license
+* a perpetual, worldwide, non-exclusive, no-charge, royalty-free, irrevocable
+* (except for failure to satisfy the conditions of this license) patent license
+* to make, have made, use, offer to sell, sell, import, and otherwise transfer
+* this software, where such license applies only to those patent claims, already
+* acquired or hereafter acquired, licensable by such copyright holder or
+* contributor that are necessarily infringed by:
+*
+* (a) their Contribution(s) (the licensed copyrights of copyright holders and
+* non-copyrightable additions of contributors, in source or binary form) alone;
+* or
+*
+* (b) combination of their Contribution(s) with the work of authorship to which
+* such Contribution(s) was added by such copyright holder or contributor, if,
+* at the time the Contribution is added, such addition causes such combination
+* to be necessarily infringed. The patent license shall not apply to any other
+* combinations which include the Contribution.
+*
+* Except as expressly stated above, no rights or licenses from any copyright
+* holder or contributor is granted under this license, whether expressly, by
+* implication, estoppel or otherwise.
+*
+* DISCLAIMER
+*
+* THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+* AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+* IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+* DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS OR CONTRIBUTORS BE LIABLE
+* FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+* DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+* SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+* CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+* OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+* OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+*/
+
+use crate::{
+    exporter::{
+        artifact::{Artifact, ArtifactKind},
+        export::*,
+        plan::Plan,
+        sign::SignTool,
+    },
+    project::Meta,
+};
+
+//================================================================
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+
+//================================================================
+
+#[derive(Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AppImage {
+    /// Exporter name, to tell apart exporters of the same kind.
+    pub name: String,
+    /// Output file name, without extension, over the project's output_name. Supports {name},
+    /// {version}, etc.
+    pub file: String,
+    /// Path to the application binary.
+    pub binary: String,
+    /// Name of a compile step whose binary to package, instead of binary.
+    #[serde(default)]
+    pub binary_from: String,
+    /// Path to a custom AppRun script.
+    pub script: String,
+    /// Names of steps that must succeed before this one is run.
+    #[serde(default)]
+    pub need: Vec<String>,
+    /// Seconds the step may run before it is stopped. 0 for no limit.
+    #[serde(default)]
+    pub timeout: u64,
+    /// Whether the exporter is run.
+    pub enable: bool,
+    #[serde(skip)]
+    pub remove: bool,
+    #[serde(skip)]
+    status: ExportStatus,
+    #[serde(skip)]
+    handler: EventHandler,
+}
+
+#[typetag::serde]
+impl Export for AppImage {
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    fn get_enable(&self) -> bool {
+        self.enable
+    }
+
+    fn get_remove(&self) -> bool {
+        self.remove
+    }
+
+    fn get_status(&mut self) -> &mut ExportStatus {
+        &mut self.status
+    }
+
+    fn get_handler(&mut self) -> &mut EventHandler {
+        &mut self.handler
+    }
+
+    fn get_path(&mut self) -> Vec<&mut String> {
+        vec![&mut self.binary, &mut self.script]
+    }
+
+    fn get_need(&self) -> &[String] {
+        &self.need
+    }
+
+    fn get_timeout(&self) -> u64 {
+        self.timeout
+    }
+
+    fn get_artifact(&self, meta: &Meta) -> anyhow::Result<Vec<Artifact>> {
+        Ok(vec![Artifact::new(
+            &self.name,
+            meta.output_path(&self.file, &self.name, std::env::consts::ARCH, "AppImage")?,
+            ArtifactKind::Package,
+            std::env::consts::ARCH,
+        )])
+    }
+
+    fn validate(&self, meta: &Meta) -> Vec<String> {
+        let mut problem = Vec::new();
+
+        if meta.name.is_empty() {
+            problem.push("Project name cannot be empty.".to_string());
+        }
+
+        if self.binary_from.is_empty() {
+            problem.extend(validate_file(meta, &self.binary, "Binary"));
+        }
+
+        problem.extend(validate_file(meta, &self.script, "AppRun script"));
+        problem.extend(validate_file(meta, &meta.icon, "Icon"));
+        problem.extend(validate_tool("appimagetool"));
+
+        problem
+    }
+
+    fn get_reference(&self) -> Vec<&str> {
+        if self.binary_from.is_empty() {
+            Vec::default()
+        } else {
+            vec![&self.binary_from]
+        }
+    }
+
+    fn plan(&self, meta: &Meta) -> anyhow::Result<Plan> {
+        let binary = meta.binary(&self.binary_from, &self.binary, std::env::consts::ARCH)?;
+        let script = meta.template.format(&self.script)?;
+        let artifact = self.get_artifact(meta)?;
+
+        let work = meta
+            .stage
+            .join("app_image")
+            .join(format!("{}.AppDir", meta.name));
+
+        let mut command = meta.command("appimagetool");
+        let mut warning = Vec::new();
+
+        // embed a signature with the project's key, which appimagetool only takes from GPG.
+        if meta.signing.tool == SignTool::Gpg && !meta.signing.is_empty() {
+            if meta.signing.key.is_empty() {
+                warning.push(
+                    "Not signed, appimagetool needs the ID of a key in your own keyring."
+                        .to_string(),
+                );
+            } else {
+                command
+                    .arg("--sign")
+                    .arg("--sign-key")
+                    .arg(&meta.signing.key);
+
+                if let Some(passphrase) = meta.signing.passphrase_value() {
+                    command.env("APPIMAGETOOL_SIGN_PASSPHRASE", passphrase);
+                }
+            }
+        }
+
+        command.arg(&work).arg(&artifact[0].path);
+
+        let mut plan = Plan::new(Some(work), Some(command));
+        plan.warning = warning;
+        plan.epoch = meta.epoch;
+
+        // copy custom AppRun script, or write the default one.
+        if !script.is_empty() {
+            plan.copy(meta.resolve(&script), "AppRun", 0o755);
+        } else {
+            plan.write("AppRun", Self::file_app_run(meta), 0o755);
+        }
+
+        // write .desktop file.
+        plan.write(
+            format!("{}.desktop", meta.name),
+            meta.create_desktop_file(true),
+            0o644,
+        );
+
+        // copy binary, if present.
+        if let Some(binary) = binary {
+            plan.copy(binary, format!("usr/bin/{}", meta.name), 0o755);
+        } else {
+            plan.warning.push("No binary to package.".to_string());
+        }
+
+        // copy icon file, if present.
+        if !meta.icon.is_empty() {
+            if !meta.icon.ends_with(".png") {
+                plan.warning
+                    .push("Icon is not a .png file, appimagetool may reject it.".to_string());
+            }
+
+            // appimagetool won't work if we don't have an extension at the end...
+            plan.copy(
+                meta.resolve(&meta.icon),
+                format!("{}-icon.png", meta.name),
+                0o644,
+            );
+        }
+
+        Ok(plan)
+    }
+
+    fn run(&mut self, meta: Meta) -> anyhow::Result<()> {
+        self.status = ExportStatus::InProgress;
+
+        let plan = self.plan(&meta)?;
+        let artifact = self.get_artifact(&meta)?;
+
+        self.execute_plan(plan, artifact);
+
+        Ok(())
+    }
+}
+
+impl AppImage {
+    const FILE_APP_RUN: &'static str = r#"#!/bin/bash
+
+export APPDIR="$(dirname "$(readlink -f "$0")")"
+export PATH="$APPDIR/usr/bin/:$PATH"
+export LD_LIBRARY_PATH="$APPDIR/usr/lib:$PATH"
+export XDG_DATA_DIRS="$APPDIR/usr/share/:/usr/share/:$XDG_DATA_DIRS"
+
+"$APPDIR"/usr/bin/{name}
+"#;
+
+    /// get the default AppRun script.
+    pub fn file_app_run(meta: &Meta) -> String {
+        let mut file = Self::FILE_APP_RUN.to_string();
+        file = file.replace("{name}", &meta.name);
+
+        file
+    }
+}
@@ -0,0 +1,194 @@
+/*
+* Copyright (c) 2025 luxreduxdelux
+*
+* Redistribution and use in source and binary forms, with or without
+* modification, are permitted provided that the following conditions are met:
+*
+* 1. Redistributions of source code must retain the above copyright notice,
+* this list of conditions and the following disclaimer.
+*
+* 2. Redistributions in binary form must reproduce the above copyright notice,
+* this list of conditions and the following disclaimer in the documentation
+* and/or other materials provided with the distribution.
+*
+* Subject to the terms and conditions of this license, each copyright holder
+* and contributor hereby grants to those receiving rights under this license
+* a perpetual, worldwide, non-exclusive, no-charge, royalty-free, irrevocable
+* (except for failure to satisfy the conditions of this license) patent license
+* to make, have made, use, offer to sell, sell, import, and otherwise transfer
+* this software, where such license applies only to those patent claims, already
+* acquired or hereafter acquired, licensable by such copyright holder or
+* contributor that are necessarily infringed by:
+*
+* (a) their Contribution(s) (the licensed copyrights of copyright holders and
+* non-copyrightable additions of contributors, in source or binary form) alone;
+* or
+*
+* (b) combination of their Contribution(s) with the work of authorship to which
+* such Contribution(s) was added by such copyright holder or contributor, if,
+* at the time the Contribution is added, such addition causes such combination
+* to be necessarily infringed. The patent license shall not apply to any other
+* combinations which include the Contribution.
+*
+* Except as expressly stated above, no rights or licenses from any copyright
+* holder or contributor is granted under this license, whether expressly, by
+* implication, estoppel or otherwise.
+*
+* DISCLAIMER
+*
+* THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+* AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+* IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+* DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS OR CONTRIBUTORS BE LIABLE
+* FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+* DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+* SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+* CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+* OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+* OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+*/
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+//================================================================
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum ArtifactKind {
+    Binary,
+    #[default]
+    Package,
+    Other,
+}
+
+impl Display for ArtifactKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArtifactKind::Binary => f.write_str("Binary"),
+            ArtifactKind::Package => f.write_str("Package"),
+            ArtifactKind::Other => f.write_str("Other"),
+        }
+    }
+}
+
+/// a file produced by a compile or package step.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Artifact {
+    /// name of the step that produces it.
+    pub step: String,
+    pub path: PathBuf,
+    pub kind: ArtifactKind,
+    /// target triple or architecture name, e.g. "x86_64-unknown-linux-gnu", "amd64" or "all".
+    pub architecture: String,
+}
+
+impl Artifact {
+    pub fn new(step: &str, path: PathBuf, kind: ArtifactKind, architecture: &str) -> Self {
+        Self {
+            step: step.to_string(),
+            path,
+            kind,
+            architecture: architecture.to_string(),
+        }
+    }
+
+    /// check if the artifact runs on an architecture, given as either a Debian or a Rust name.
+    pub fn architecture_match(&self, architecture: &str) -> bool {
+        let a = Self::architecture_family(&self.architecture);
+        let b = Self::architecture_family(architecture);
+
+        a == "all" || b == "all" || a == b
+    }
+
+    /// get the Rust name of an architecture, from a Debian name or a target triple.
+    fn architecture_family(architecture: &str) -> &str {
+        let architecture = architecture.split('-').next().unwrap_or_default();
+
+        match architecture {
+            "amd64" => "x86_64",
+            "arm64" => "aarch64",
+            "i386" | "i586" | "i686" => "x86",
+            "armhf" | "armel" | "Armel" | "armv7" | "armv5te" => "arm",
+            "mips64el" => "mips64",
+            "ppc64el" | "powerpc64le" => "powerpc64",
+            _ => architecture,
+        }
+    }
+
+    /// check that the artifact exists and is newer than every file in its sources.
+    pub fn check(&self, source: &[PathBuf]) -> anyhow::Result<()> {
+        let time = std::fs::metadata(&self.path)
+            .and_then(|x| x.modified())
+            .map_err(|_| {
+                anyhow::Error::msg(format!(
+                    "Artifact \"{}\" of step \"{}\" does not exist, run the compile step first.",
+                    self.path.display(),
+                    self.step
+                ))
+            })?;
+
+        for path in source {
+            if let Some((file, newest)) = Self::newest(path)
+                && newest > time
+            {
+                return Err(anyhow::Error::msg(format!(
+                    "Artifact \"{}\" of step \"{}\" is older than \"{}\", run the compile step again.",
+                    self.path.display(),
+                    self.step,
+                    file.display()
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// find the most recently modified file in a folder, skipping build output, Boondle's own folders and hidden folders.
+    fn newest(path: &Path) -> Option<(PathBuf, SystemTime)> {
+        let metadata = std::fs::symlink_metadata(path).ok()?;
+
+        if !metadata.is_dir() {
+            return Some((path.to_path_buf(), metadata.modified().ok()?));
+        }
+
+        std::fs::read_dir(path)
+            .ok()?
+            .flatten()
+            .filter(|entry| {
+                let name = entry.file_name();
+                let name = name.to_string_lossy();
+
+                !name.starts_with('.') && !name.starts_with("boondle_") && name != "target"
+            })
+            .filter_map(|entry| Self::newest(&entry.path()))
+            .max_by_key(|(_, time)| *time)
+    }
+}
+
+//================================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn architecture() {
+        let artifact = Artifact::new(
+            "app",
+            PathBuf::from("app"),
+            ArtifactKind::Binary,
+            "x86_64-unknown-linux-gnu",
+        );
+
+        assert!(artifact.architecture_match("amd64"));
+        assert!(artifact.architecture_match("x86_64"));
+        assert!(artifact.architecture_match("all"));
+        assert!(!artifact.architecture_match("arm64"));
+        assert!(!artifact.architecture_match("i386"));
+    }
+}
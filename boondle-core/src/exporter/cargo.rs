@@ -0,0 +1,534 @@
+/*
+* Copyright (c) 2025 luxreduxdelux
+*
+* Redistribution and use in source and binary forms, with or without
+* modification, are permitted provided that the following conditions are met:
+*
+* 1. Redistributions of source code must retain the above copyright notice,
+* this list of conditions and the following disclaimer.
+*
+* 2. Redistributions in binary form must reproduce the above copyright notice,
+* this list of conditions and the following disclaimer in the documentation
+* and/or other materials provided with the distribution.
+*
+* Subject to the terms and conditions of this license, each copyright holder
+* and contributor hereby grants to those receiving rights under this license
+* a perpetual, worldwide, non-exclusive, no-charge, royalty-free, irrevocable
+* (except for failure to satisfy the conditions of this license) patent license
+* to make, have made, use, offer to sell, sell, import, and otherwise transfer
+* this software, where such license applies only to those patent claims, already
+* acquired or hereafter acquired, licensable by such copyright holder or
+* contributor that are necessarily infringed by:
+*
+* (a) their Contribution(s) (the licensed copyrights of copyright holders and
+* non-copyrightable additions of contributors, in source or binary form) alone;
+* or
+*
+* (b) combination of their Contribution(s) with the work of authorship to which
+* such Contribution(s) was added by such copyright holder or contributor, if,
+* at the time the Contribution is added, such addition causes such combination
+* to be necessarily infringed. The patent license shall not apply to any other
+* combinations which include the Contribution.
+*
+* Except as expressly stated above, no rights or licenses from any copyright
+* holder or contributor is granted under this license, whether expressly, by
+* implication, estoppel or otherwise.
+*
+* DISCLAIMER
+*
+* THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+* AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+* IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+* DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS OR CONTRIBUTORS BE LIABLE
+* FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+* DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+* SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+* CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+* OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+* OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+*/
+
+use crate::{
+    exporter::{
+        artifact::{Artifact, ArtifactKind},
+        export::*,
+        plan::Plan,
+    },
+    project::Meta,
+};
+
+//================================================================
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::{
+    any::Any,
+    path::{Path, PathBuf},
+};
+
+//================================================================
+
+#[derive(Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Cargo {
+    /// Exporter name, to tell apart exporters of the same kind. Named steps expose their
+    /// binaries as {compile.NAME.binary} and {compile.NAME.binary.TARGET}.
+    pub name: String,
+    /// Path to the Cargo.toml to build. Defaults to the project's Cargo manifest.
+    pub manifest: String,
+    /// Workspace package to build. Defaults to the manifest's own package.
+    pub package: String,
+    /// Binary target to build. Defaults to every binary of the package.
+    pub binary: String,
+    /// Cargo profile to build with. Defaults to "release".
+    pub profile: String,
+    /// Target triples to build for. Defaults to the host.
+    pub target: Vec<String>,
+    /// Features to enable.
+    pub feature: Vec<String>,
+    /// Whether to disable the package's default features.
+    pub no_default_features: bool,
+    /// Linker to cross-compile with, for every target.
+    pub linker: String,
+    /// Whether to build with `cross` instead of `cargo`.
+    pub cross: bool,
+    /// Names of steps that must succeed before this one is run.
+    #[serde(default)]
+    pub need: Vec<String>,
+    /// Seconds the step may run before it is stopped. 0 for no limit.
+    #[serde(default)]
+    pub timeout: u64,
+    /// Whether the exporter is run.
+    pub enable: bool,
+    #[serde(skip)]
+    pub remove: bool,
+    #[serde(skip)]
+    status: ExportStatus,
+    #[serde(skip)]
+    handler: EventHandler,
+}
+
+#[typetag::serde]
+impl Export for Cargo {
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    fn get_enable(&self) -> bool {
+        self.enable
+    }
+
+    fn get_remove(&self) -> bool {
+        self.remove
+    }
+
+    fn get_status(&mut self) -> &mut ExportStatus {
+        &mut self.status
+    }
+
+    fn get_handler(&mut self) -> &mut EventHandler {
+        &mut self.handler
+    }
+
+    fn get_path(&mut self) -> Vec<&mut String> {
+        vec![&mut self.manifest]
+    }
+
+    fn get_need(&self) -> &[String] {
+        &self.need
+    }
+
+    fn get_timeout(&self) -> u64 {
+        self.timeout
+    }
+
+    fn get_artifact(&self, meta: &Meta) -> anyhow::Result<Vec<Artifact>> {
+        let package = self.package_info(meta)?;
+        let folder = self.profile_folder(meta)?;
+        let mut result = Vec::new();
+
+        for binary in &package.binary {
+            if self.target.is_empty() {
+                result.push(Artifact::new(
+                    &self.name,
+                    package.target_folder.join(&folder).join(binary),
+                    ArtifactKind::Binary,
+                    std::env::consts::ARCH,
+                ));
+            }
+
+            for target in &self.target {
+                let target = meta.template.format(target)?;
+                let mut file = binary.clone();
+
+                if target.contains("windows") {
+                    file.push_str(".exe");
+                }
+
+                result.push(Artifact::new(
+                    &self.name,
+                    package.target_folder.join(&target).join(&folder).join(file),
+                    ArtifactKind::Binary,
+                    &target,
+                ));
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn get_source(&self, meta: &Meta) -> anyhow::Result<Vec<PathBuf>> {
+        let manifest = self.manifest(meta)?;
+        let metadata = Self::metadata(&manifest, true)?;
+        let package_name = meta.template.format(&self.package)?;
+        let list = metadata["packages"].as_array().cloned().unwrap_or_default();
+        let package = Self::package_find(&list, &manifest, &package_name)?;
+
+        // walk every package the package depends on, however deeply.
+        let node = metadata["resolve"]["nodes"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        let mut visit = vec![package["id"].clone()];
+        let mut seen = Vec::new();
+
+        while let Some(id) = visit.pop() {
+            if seen.contains(&id) {
+                continue;
+            }
+
+            if let Some(node) = node.iter().find(|x| x["id"] == id) {
+                visit.extend(node["dependencies"].as_array().cloned().unwrap_or_default());
+            }
+
+            seen.push(id);
+        }
+
+        // only local packages can change under a build: path dependencies and workspace members.
+        // a package from a registry or a git repository has a source.
+        let mut result: Vec<PathBuf> = list
+            .iter()
+            .filter(|x| seen.contains(&x["id"]) && x["source"].is_null())
+            .filter_map(|x| x["manifest_path"].as_str().map(PathBuf::from))
+            .filter_map(|x| x.parent().map(Self::source))
+            .flatten()
+            .collect();
+
+        if let Some(root) = metadata["workspace_root"].as_str() {
+            result.push(Path::new(root).join("Cargo.toml"));
+            result.push(Path::new(root).join("Cargo.lock"));
+        }
+
+        result.sort();
+        result.dedup();
+
+        Ok(result)
+    }
+
+    fn validate(&self, meta: &Meta) -> Vec<String> {
+        let mut problem = Vec::new();
+
+        if self.cross && self.target.len() > 1 {
+            problem.push("cross can only build for one target at a time.".to_string());
+        }
+
+        match self.manifest(meta) {
+            Ok(manifest) if !manifest.is_file() => problem.push(format!(
+                "Manifest \"{}\" does not exist.",
+                manifest.display()
+            )),
+            Err(error) => problem.push(error.to_string()),
+            _ => {}
+        }
+
+        problem.extend(validate_tool(if self.cross { "cross" } else { "cargo" }));
+
+        match meta.template.format(&self.linker) {
+            Ok(linker) if !linker.is_empty() => problem.extend(validate_tool(&linker)),
+            Err(error) => problem.push(error.to_string()),
+            _ => {}
+        }
+
+        problem
+    }
+
+    fn plan(&self, meta: &Meta) -> anyhow::Result<Plan> {
+        let manifest = self.manifest(meta)?;
+
+        let mut command = meta.command(if self.cross { "cross" } else { "cargo" });
+        command.current_dir(&meta.path);
+        command.arg("build").arg("--manifest-path").arg(&manifest);
+        command.arg("--profile").arg(self.profile(meta)?);
+
+        if !self.package.is_empty() {
+            command
+                .arg("--package")
+                .arg(meta.template.format(&self.package)?);
+        }
+
+        if !self.binary.is_empty() {
+            command
+                .arg("--bin")
+                .arg(meta.template.format(&self.binary)?);
+        }
+
+        if self.no_default_features {
+            command.arg("--no-default-features");
+        }
+
+        if !self.feature.is_empty() {
+            let feature: anyhow::Result<Vec<_>> = self
+                .feature
+                .iter()
+                .map(|x| meta.template.format(x))
+                .collect();
+
+            command.arg("--features").arg(feature?.join(","));
+        }
+
+        let linker = meta.template.format(&self.linker)?;
+
+        for target in &self.target {
+            let target = meta.template.format(target)?;
+
+            // e.g. CARGO_TARGET_AARCH64_UNKNOWN_LINUX_GNU_LINKER.
+            if !linker.is_empty() {
+                command.env(
+                    format!(
+                        "CARGO_TARGET_{}_LINKER",
+                        target.to_uppercase().replace(['-', '.'], "_")
+                    ),
+                    &linker,
+                );
+            }
+
+            command.arg("--target").arg(target);
+        }
+
+        Ok(Plan::new(None, Some(command)))
+    }
+
+    fn run(&mut self, meta: Meta) -> anyhow::Result<()> {
+        self.status = ExportStatus::InProgress;
+
+        let plan = self.plan(&meta)?;
+        let artifact = self.get_artifact(&meta)?;
+
+        self.execute_plan(plan, artifact);
+
+        Ok(())
+    }
+}
+
+/// what `cargo metadata` tells about the package to build.
+struct PackageInfo {
+    /// Cargo's target directory.
+    target_folder: PathBuf,
+    /// binary targets to build.
+    binary: Vec<String>,
+}
+
+impl Cargo {
+    /// files and folders of a crate it is built from. the rest of the crate's folder is left out,
+    /// as it may hold the project, its output folder or its staging folder.
+    const SOURCE: [&str; 4] = ["src", "Cargo.toml", "Cargo.lock", "build.rs"];
+
+    /// get the files and folders of a crate it is built from.
+    fn source(folder: &Path) -> Vec<PathBuf> {
+        Self::SOURCE.iter().map(|x| folder.join(x)).collect()
+    }
+
+    /// get the manifest to build, falling back to the project's Cargo manifest.
+    fn manifest(&self, meta: &Meta) -> anyhow::Result<PathBuf> {
+        let manifest = meta.template.format(&self.manifest)?;
+
+        if !manifest.is_empty() {
+            Ok(meta.resolve(&manifest))
+        } else if !meta.cargo.is_empty() {
+            Ok(meta.resolve(&meta.cargo))
+        } else {
+            Ok(meta.resolve("Cargo.toml"))
+        }
+    }
+
+    fn profile(&self, meta: &Meta) -> anyhow::Result<String> {
+        if self.profile.is_empty() {
+            Ok("release".to_string())
+        } else {
+            meta.template.format(&self.profile)
+        }
+    }
+
+    /// get the folder name Cargo writes a profile's output to.
+    fn profile_folder(&self, meta: &Meta) -> anyhow::Result<String> {
+        let profile = self.profile(meta)?;
+
+        Ok(match profile.as_str() {
+            "dev" | "test" => "debug".to_string(),
+            "bench" => "release".to_string(),
+            _ => profile,
+        })
+    }
+
+    /// get the metadata of a manifest, with every dependency resolved if asked to.
+    fn metadata(manifest: &Path, dependency: bool) -> anyhow::Result<serde_json::Value> {
+        let mut command = std::process::Command::new("cargo");
+        command.arg("metadata").arg("--format-version").arg("1");
+
+        if !dependency {
+            command.arg("--no-deps");
+        }
+
+        let out = command.arg("--manifest-path").arg(manifest).output()?;
+
+        if !out.status.success() {
+            return Err(anyhow::Error::msg(format!(
+                "Cargo: \"cargo metadata\" failed: {}",
+                String::from_utf8_lossy(&out.stderr).trim()
+            )));
+        }
+
+        Ok(serde_json::from_slice(&out.stdout)?)
+    }
+
+    /// find the package to build in the metadata: the one named, or else the one of the manifest.
+    fn package_find<'a>(
+        list: &'a [serde_json::Value],
+        manifest: &Path,
+        name: &str,
+    ) -> anyhow::Result<&'a serde_json::Value> {
+        let manifest = std::fs::canonicalize(manifest)?;
+
+        list.iter()
+            .find(|package| {
+                if name.is_empty() {
+                    package["manifest_path"].as_str().map(PathBuf::from) == Some(manifest.clone())
+                } else {
+                    package["name"] == name
+                }
+            })
+            .ok_or(anyhow::Error::msg(
+                "Cargo: Could not find the package to build, set the step's package.",
+            ))
+    }
+
+    /// find the package to build, and the binary targets to build from it.
+    fn package_info(&self, meta: &Meta) -> anyhow::Result<PackageInfo> {
+        let manifest = self.manifest(meta)?;
+        let metadata = Self::metadata(&manifest, false)?;
+        let package_name = meta.template.format(&self.package)?;
+        let list = metadata["packages"].as_array().cloned().unwrap_or_default();
+        let package = Self::package_find(&list, &manifest, &package_name)?;
+
+        let target_folder = metadata["target_directory"]
+            .as_str()
+            .map(PathBuf::from)
+            .ok_or(anyhow::Error::msg(
+                "Cargo: \"cargo metadata\" has no target directory.",
+            ))?;
+
+        let binary: Vec<String> = if !self.binary.is_empty() {
+            vec![meta.template.format(&self.binary)?]
+        } else {
+            package["targets"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter(|target| {
+                    target["kind"]
+                        .as_array()
+                        .is_some_and(|kind| kind.iter().any(|x| x == "bin"))
+                })
+                .filter_map(|target| target["name"].as_str().map(str::to_string))
+                .collect()
+        };
+
+        if binary.is_empty() {
+            return Err(anyhow::Error::msg(format!(
+                "Cargo: Package \"{}\" has no binary target.",
+                package["name"].as_str().unwrap_or_default()
+            )));
+        }
+
+        Ok(PackageInfo {
+            target_folder,
+            binary,
+        })
+    }
+}
+
+//================================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// write a file, creating its folder.
+    fn write(path: &Path, data: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, data).unwrap();
+    }
+
+    /// set the time a file was last modified to a minute from now.
+    fn touch(path: &Path) {
+        let later = std::time::SystemTime::now() + std::time::Duration::from_secs(60);
+
+        std::fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+    }
+
+    #[test]
+    fn source() {
+        let path = std::env::temp_dir().join(format!("boondle_cargo_{}", std::process::id()));
+        let app = path.join("app");
+
+        write(
+            &app.join("Cargo.toml"),
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\nedition = \"2024\"\n\n[dependencies]\nlib = { path = \"../lib\" }\n",
+        );
+        write(&app.join("src/main.rs"), "fn main() {}\n");
+        write(
+            &path.join("lib/Cargo.toml"),
+            "[package]\nname = \"lib\"\nversion = \"0.1.0\"\nedition = \"2024\"\n",
+        );
+        write(&path.join("lib/src/lib.rs"), "");
+        write(&path.join("other/src/lib.rs"), "");
+
+        let meta = Meta {
+            path: app.clone(),
+            ..Default::default()
+        };
+        // with no manifest set, the step builds the one in the project folder.
+        let step = Cargo::default();
+
+        let source = step.get_source(&meta).unwrap();
+        assert!(source.contains(&app.join("src")));
+        assert!(source.contains(&path.join("lib/src")));
+
+        let binary = app.join("target/release/app");
+        write(&binary, "");
+        let artifact = Artifact::new("app", binary, ArtifactKind::Binary, "x86_64");
+        assert!(artifact.check(&source).is_ok());
+
+        // a package written into the project's output folder, within the crate, or a change to a
+        // folder the crate doesn't depend on, must not make the binary stale.
+        write(&app.join("boondle/app.deb"), "");
+        touch(&app.join("boondle/app.deb"));
+        touch(&path.join("other/src/lib.rs"));
+        assert!(artifact.check(&source).is_ok());
+
+        // a change to a path dependency does.
+        touch(&path.join("lib/src/lib.rs"));
+        assert!(artifact.check(&source).is_err());
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+}
@@ -0,0 +1,368 @@
+/*
+* Copyright (c) 2025 luxreduxdelux
+*
+* Redistribution and use in source and binary forms, with or without
+* modification, are permitted provided that the following conditions are met:
+*
+* 1. Redistributions of source code must retain the above copyright notice,
+* this list of conditions and the following disclaimer.
+*
+* 2. Redistributions in binary form must reproduce the above copyright notice,
+* this list of conditions and the following disclaimer in the documentation
+* and/or other materials provided with the distribution.
+*
+* Subject to the terms and conditions of this license, each copyright holder
+* and contributor hereby grants to those receiving rights under this license
+* a perpetual, worldwide, non-exclusive, no-charge, royalty-free, irrevocable
+* (except for failure to satisfy the conditions of this license) patent license
+* to make, have made, use, offer to sell, sell, import, and otherwise transfer
+* this software, where such license applies only to those patent claims, already
+* acquired or hereafter acquired, licensable by such copyright holder or
+* contributor that are necessarily infringed by:
+*
+* (a) their Contribution(s) (the licensed copyrights of copyright holders and
+* non-copyrightable additions of contributors, in source or binary form) alone;
+* or
+*
+* (b) combination of their Contribution(s) with the work of authorship to which
+* such Contribution(s) was added by such copyright holder or contributor, if,
+* at the time the Contribution is added, such addition causes such combination
+* to be necessarily infringed. The patent license shall not apply to any other
+* combinations which include the Contribution.
+*
+* Except as expressly stated above, no rights or licenses from any copyright
+* holder or contributor is granted under this license, whether expressly, by
+* implication, estoppel or otherwise.
+*
+* DISCLAIMER
+*
+* THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+* AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+* IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+* DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS OR CONTRIBUTORS BE LIABLE
+* FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+* DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+* SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+* CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+* OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+* OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+*/
+
+use crate::{
+    exporter::{
+        artifact::{Artifact, ArtifactKind},
+        export::*,
+        plan::Plan,
+    },
+    project::Meta,
+};
+
+//================================================================
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha512};
+use std::{
+    any::Any,
+    fmt::Display,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+//================================================================
+
+/// hash function of a checksum manifest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hash {
+    Sha256,
+    Sha512,
+}
+
+impl Hash {
+    pub const LIST: [Self; 2] = [Self::Sha256, Self::Sha512];
+
+    /// get the file name of the manifest, as written by `sha256sum` and `sha512sum`.
+    pub fn file(&self) -> &'static str {
+        match self {
+            Self::Sha256 => "SHA256SUMS",
+            Self::Sha512 => "SHA512SUMS",
+        }
+    }
+
+    /// hash a file, in lowercase hexadecimal.
+    pub fn hash(&self, path: &Path) -> anyhow::Result<String> {
+        let mut file = std::fs::File::open(path)?;
+
+        match self {
+            Self::Sha256 => Self::digest::<Sha256>(&mut file),
+            Self::Sha512 => Self::digest::<Sha512>(&mut file),
+        }
+    }
+
+    fn digest<D: Digest + Write>(file: &mut std::fs::File) -> anyhow::Result<String> {
+        let mut digest = D::new();
+        std::io::copy(file, &mut digest)?;
+
+        Ok(digest
+            .finalize()
+            .iter()
+            .map(|x| format!("{x:02x}"))
+            .collect())
+    }
+
+    /// get the manifest of every file, in the format `sha256sum --check` reads. a file in the
+    /// manifest's folder is listed by its path relative to it.
+    pub fn manifest(&self, folder: &Path, list: &[PathBuf]) -> anyhow::Result<String> {
+        let mut result = String::new();
+
+        for path in list {
+            let name = path.strip_prefix(folder).unwrap_or(path);
+
+            result.push_str(&format!("{}  {}\n", self.hash(path)?, name.display()));
+        }
+
+        Ok(result)
+    }
+}
+
+/// outcome of checking one file against a checksum manifest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Match,
+    Mismatch,
+    Missing,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Match => f.write_str("OK"),
+            Verdict::Mismatch => f.write_str("FAILED"),
+            Verdict::Missing => f.write_str("MISSING"),
+        }
+    }
+}
+
+/// check every file listed in every checksum manifest of a folder, returning each file with its
+/// manifest and outcome. fails if the folder has no manifest, or one can't be read.
+pub fn verify(folder: &Path) -> anyhow::Result<Vec<(PathBuf, Hash, Verdict)>> {
+    let mut result = Vec::new();
+    let mut found = false;
+
+    for hash in Hash::LIST {
+        let manifest = folder.join(hash.file());
+
+        if !manifest.is_file() {
+            continue;
+        }
+
+        found = true;
+
+        for (i, line) in std::fs::read_to_string(&manifest)?.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            // the name may be marked as read in binary mode.
+            let Some((expect, name)) = line.split_once(' ') else {
+                return Err(anyhow::Error::msg(format!(
+                    "Verify: Line {} of \"{}\" is not a checksum.",
+                    i + 1,
+                    manifest.display()
+                )));
+            };
+            let name = name.strip_prefix([' ', '*']).unwrap_or(name);
+            let path = folder.join(name);
+
+            let verdict = match hash.hash(&path) {
+                Ok(actual) if actual.eq_ignore_ascii_case(expect) => Verdict::Match,
+                Ok(_) => Verdict::Mismatch,
+                Err(_) => Verdict::Missing,
+            };
+
+            result.push((PathBuf::from(name), hash, verdict));
+        }
+    }
+
+    if !found {
+        return Err(anyhow::Error::msg(format!(
+            "Verify: No SHA256SUMS or SHA512SUMS in \"{}\".",
+            folder.display()
+        )));
+    }
+
+    Ok(result)
+}
+
+//================================================================
+
+#[derive(Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Checksum {
+    /// Exporter name, to tell apart exporters of the same kind.
+    pub name: String,
+    /// Whether to also write SHA512SUMS, next to SHA256SUMS.
+    #[serde(default)]
+    pub sha512: bool,
+    /// Names of steps that must succeed before this one is run.
+    #[serde(default)]
+    pub need: Vec<String>,
+    /// Seconds the step may run before it is stopped. 0 for no limit.
+    #[serde(default)]
+    pub timeout: u64,
+    /// Whether the exporter is run.
+    pub enable: bool,
+    #[serde(skip)]
+    pub remove: bool,
+    #[serde(skip)]
+    status: ExportStatus,
+    #[serde(skip)]
+    handler: EventHandler,
+}
+
+impl Checksum {
+    /// get every hash function to write a manifest for.
+    pub fn hash(&self) -> Vec<Hash> {
+        if self.sha512 {
+            Hash::LIST.to_vec()
+        } else {
+            vec![Hash::Sha256]
+        }
+    }
+}
+
+#[typetag::serde]
+impl Export for Checksum {
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    fn get_enable(&self) -> bool {
+        self.enable
+    }
+
+    fn get_remove(&self) -> bool {
+        self.remove
+    }
+
+    fn get_status(&mut self) -> &mut ExportStatus {
+        &mut self.status
+    }
+
+    fn get_handler(&mut self) -> &mut EventHandler {
+        &mut self.handler
+    }
+
+    fn get_path(&mut self) -> Vec<&mut String> {
+        Vec::default()
+    }
+
+    fn get_need(&self) -> &[String] {
+        &self.need
+    }
+
+    fn get_timeout(&self) -> u64 {
+        self.timeout
+    }
+
+    // after every package is built and signed.
+    fn get_rank(&self) -> u32 {
+        2
+    }
+
+    fn get_artifact(&self, meta: &Meta) -> anyhow::Result<Vec<Artifact>> {
+        Ok(self
+            .hash()
+            .iter()
+            .map(|hash| {
+                Artifact::new(
+                    &self.name,
+                    meta.output.join(hash.file()),
+                    ArtifactKind::Other,
+                    "all",
+                )
+            })
+            .collect())
+    }
+
+    fn plan(&self, meta: &Meta) -> anyhow::Result<Plan> {
+        let source: Vec<PathBuf> = meta
+            .artifact
+            .iter()
+            .filter(|x| x.kind == ArtifactKind::Package)
+            .map(|x| x.path.clone())
+            .collect();
+
+        let mut plan = Plan::new(Some(meta.output.clone()), None);
+
+        if source.is_empty() {
+            plan.warning
+                .push("No package was produced, so there is nothing to checksum.".to_string());
+            return Ok(plan);
+        }
+
+        for hash in self.hash() {
+            plan.hash(source.clone(), hash, 0o644);
+        }
+
+        Ok(plan)
+    }
+
+    fn run(&mut self, meta: Meta) -> anyhow::Result<()> {
+        self.status = ExportStatus::InProgress;
+
+        let plan = self.plan(&meta)?;
+        let artifact = if plan.action.is_empty() {
+            Vec::default()
+        } else {
+            self.get_artifact(&meta)?
+        };
+
+        self.execute_plan(plan, artifact);
+
+        Ok(())
+    }
+}
+
+//================================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn verify() {
+        let path = std::env::temp_dir().join(format!("boondle_checksum_{}", std::process::id()));
+        std::fs::create_dir_all(&path).unwrap();
+
+        let file = path.join("app.deb");
+        std::fs::write(&file, "abc").unwrap();
+
+        let manifest = Hash::Sha256
+            .manifest(&path, std::slice::from_ref(&file))
+            .unwrap();
+        assert_eq!(
+            manifest,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  app.deb\n"
+        );
+
+        std::fs::write(path.join("SHA256SUMS"), manifest).unwrap();
+        assert_eq!(
+            super::verify(&path).unwrap(),
+            vec![(PathBuf::from("app.deb"), Hash::Sha256, Verdict::Match)]
+        );
+
+        std::fs::write(&file, "abd").unwrap();
+        assert_eq!(super::verify(&path).unwrap()[0].2, Verdict::Mismatch);
+
+        std::fs::remove_file(&file).unwrap();
+        assert_eq!(super::verify(&path).unwrap()[0].2, Verdict::Missing);
+
+        std::fs::remove_dir_all(&path).unwrap();
+        assert!(super::verify(&path).is_err());
+    }
+}
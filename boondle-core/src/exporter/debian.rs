@@ -0,0 +1,310 @@
+/*
+* Copyright (c) 2025 luxreduxdelux
+*
+* Redistribution and use in source and binary forms, with or without
+* modification, are permitted provided that the following conditions are met:
+*
+* 1. Redistributions of source code must retain the above copyright notice,
+* this list of conditions and the following disclaimer.
+*
+* 2. Redistributions in binary form must reproduce the above copyright notice,
+* this list of conditions and the following disclaimer in the documentation
+* and/or other materials provided with the distribution.
+*
+* Subject to the terms and conditions of this license, each copyright holder
+* and contributor hereby grants to those receiving rights under this license
+* a perpetual, worldwide, non-exclusive, no-charge, royalty-free, irrevocable
+* (except for failure to satisfy the conditions of this license) patent license
+* to make, have made, use, offer to sell, sell, import, and otherwise transfer
+* this software, where such license applies only to those patent claims, already
+* acquired or hereafter acquired, licensable by such copyright holder or
+* contributor that are necessarily infringed by:
+*
+* (a) their Contribution(s) (the licensed copyrights of copyright holders and
+* non-copyrightable additions of contributors, in source or binary form) alone;
+* or
+*
+* (b) combination of their Contribution(s) with the work of authorship to which
+* such Contribution(s) was added by such copyright holder or contributor, if,
+* at the time the Contribution is added, such addition causes such combination
+* to be necessarily infringed. The patent license shall not apply to any other
+* combinations which include the Contribution.
+*
+* Except as expressly stated above, no rights or licenses from any copyright
+* holder or contributor is granted under this license, whether expressly, by
+* implication, estoppel or otherwise.
+*
+* DISCLAIMER
+*
+* THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+* AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+* IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+* DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS OR CONTRIBUTORS BE LIABLE
+* FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+* DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+* SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+* CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+* OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+* OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+*/
+
+use crate::{
+    exporter::{
+        artifact::{Artifact, ArtifactKind},
+        export::*,
+        plan::Plan,
+    },
+    project::Meta,
+};
+
+//================================================================
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+
+//================================================================
+
+#[derive(Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Debian {
+    /// Exporter name, to tell apart exporters of the same kind.
+    pub name: String,
+    /// Output file name, without extension, over the project's output_name. Supports {name},
+    /// {version}, etc.
+    pub file: String,
+    /// Path to the application binary.
+    pub binary: String,
+    /// Name of a compile step whose binary to package, instead of binary.
+    #[serde(default)]
+    pub binary_from: String,
+    /// Path to the pre-installation script.
+    pub script_prior: String,
+    /// Path to the post-installation script.
+    pub script_after: String,
+    /// Debian architecture of the package.
+    #[schemars(extend("enum" = Debian::LIST_ARCHITECTURE))]
+    pub architecture: String,
+    /// Names of steps that must succeed before this one is run.
+    #[serde(default)]
+    pub need: Vec<String>,
+    /// Seconds the step may run before it is stopped. 0 for no limit.
+    #[serde(default)]
+    pub timeout: u64,
+    /// Whether the exporter is run.
+    pub enable: bool,
+    #[serde(skip)]
+    pub remove: bool,
+    #[serde(skip)]
+    status: ExportStatus,
+    #[serde(skip)]
+    handler: EventHandler,
+}
+
+#[typetag::serde]
+impl Export for Debian {
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    fn get_enable(&self) -> bool {
+        self.enable
+    }
+
+    fn get_remove(&self) -> bool {
+        self.remove
+    }
+
+    fn get_status(&mut self) -> &mut ExportStatus {
+        &mut self.status
+    }
+
+    fn get_handler(&mut self) -> &mut EventHandler {
+        &mut self.handler
+    }
+
+    fn get_path(&mut self) -> Vec<&mut String> {
+        vec![
+            &mut self.binary,
+            &mut self.script_prior,
+            &mut self.script_after,
+        ]
+    }
+
+    fn get_need(&self) -> &[String] {
+        &self.need
+    }
+
+    fn get_timeout(&self) -> u64 {
+        self.timeout
+    }
+
+    fn get_artifact(&self, meta: &Meta) -> anyhow::Result<Vec<Artifact>> {
+        Ok(vec![Artifact::new(
+            &self.name,
+            meta.output_path(&self.file, &self.name, &self.architecture, "deb")?,
+            ArtifactKind::Package,
+            &self.architecture,
+        )])
+    }
+
+    fn validate(&self, meta: &Meta) -> Vec<String> {
+        let mut meta = meta.clone();
+        meta.template = meta.template.with("arch", &self.architecture);
+        let mut problem = Vec::new();
+
+        for (field, label) in [
+            (&meta.name, "name"),
+            (&meta.version, "version"),
+            (&meta.from, "author"),
+            (&meta.info, "info"),
+        ] {
+            if field.is_empty() {
+                problem.push(format!("Project {label} cannot be empty."));
+            }
+        }
+
+        if self.architecture.is_empty() {
+            problem.push("Architecture cannot be empty.".to_string());
+        }
+
+        if self.binary_from.is_empty() {
+            problem.extend(validate_file(&meta, &self.binary, "Binary"));
+        }
+
+        problem.extend(validate_file(
+            &meta,
+            &self.script_prior,
+            "Prior-installation script",
+        ));
+        problem.extend(validate_file(
+            &meta,
+            &self.script_after,
+            "After-installation script",
+        ));
+        problem.extend(validate_file(&meta, &meta.icon, "Icon"));
+        problem.extend(validate_tool("dpkg-deb"));
+
+        problem
+    }
+
+    fn get_reference(&self) -> Vec<&str> {
+        if self.binary_from.is_empty() {
+            Vec::default()
+        } else {
+            vec![&self.binary_from]
+        }
+    }
+
+    fn plan(&self, meta: &Meta) -> anyhow::Result<Plan> {
+        let mut meta = meta.clone();
+        meta.template = meta.template.with("arch", &self.architecture);
+
+        let artifact = self.get_artifact(&meta)?;
+        let binary = meta.binary(&self.binary_from, &self.binary, &self.architecture)?;
+        let script_prior = meta.template.format(&self.script_prior)?;
+        let script_after = meta.template.format(&self.script_after)?;
+
+        let work = meta.stage.join("debian").join(format!(
+            "{}_{}_{}",
+            meta.name, meta.version, self.architecture
+        ));
+
+        // every file is owned by root, whoever builds the package.
+        let mut command = meta.command("dpkg-deb");
+        command.arg("--root-owner-group");
+        command.arg("--build").arg(&work).arg(&artifact[0].path);
+
+        let mut plan = Plan::new(Some(work), Some(command));
+        plan.epoch = meta.epoch;
+
+        // write control file.
+        plan.write("DEBIAN/control", self.file_control(&meta), 0o644);
+
+        // copy prior-install script, if present.
+        if !script_prior.is_empty() {
+            plan.copy(meta.resolve(&script_prior), "DEBIAN/preinst", 0o755);
+        }
+
+        // copy after-install script, if present.
+        if !script_after.is_empty() {
+            plan.copy(meta.resolve(&script_after), "DEBIAN/postinst", 0o755);
+        }
+
+        // copy binary, if present.
+        if let Some(binary) = binary {
+            plan.copy(binary, format!("usr/bin/{}", meta.name), 0o755);
+        } else {
+            plan.warning.push("No binary to package.".to_string());
+        }
+
+        // write .desktop file.
+        plan.write(
+            format!("usr/share/applications/{}.desktop", meta.name),
+            meta.create_desktop_file(false),
+            0o644,
+        );
+
+        // copy icon file, if present.
+        if !meta.icon.is_empty() {
+            plan.copy(
+                meta.resolve(&meta.icon),
+                format!("usr/share/icons/{}-icon", meta.name),
+                0o644,
+            );
+        }
+
+        Ok(plan)
+    }
+
+    fn run(&mut self, meta: Meta) -> anyhow::Result<()> {
+        self.status = ExportStatus::InProgress;
+
+        let plan = self.plan(&meta)?;
+        let artifact = self.get_artifact(&meta)?;
+
+        self.execute_plan(plan, artifact);
+
+        Ok(())
+    }
+}
+
+impl Debian {
+    pub const LIST_ARCHITECTURE: [&'static str; 9] = [
+        "all", "Armel", "armhf", "arm64", "i386", "amd64", "mips64el", "ppc64el", "s390x",
+    ];
+
+    const FILE_CONTROL: &'static str = r#"Package: {name}
+Version: {version}
+Architecture: {architecture}
+Essential: no
+Priority: optional
+Depends:
+Maintainer: {from}
+Description: {info}
+{homepage}"#;
+
+    /// get the control file, from the exporter's current fields.
+    pub fn file_control(&self, meta: &Meta) -> String {
+        let mut file = Self::FILE_CONTROL.to_string();
+        file = file.replace("{name}", &meta.name);
+        file = file.replace("{info}", &meta.info);
+        file = file.replace("{from}", &meta.from);
+        file = file.replace("{version}", &meta.version);
+        file = file.replace("{architecture}", &self.architecture);
+        file = file.replace(
+            "{homepage}",
+            &if meta.homepage.is_empty() {
+                String::default()
+            } else {
+                format!("Homepage: {}\n", meta.homepage)
+            },
+        );
+
+        file
+    }
+}
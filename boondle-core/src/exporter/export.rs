@@ -0,0 +1,734 @@
+/*
+* Copyright (c) 2025 luxreduxdelux
+*
+* Redistribution and use in source and binary forms, with or without
+* modification, are permitted provided that the following conditions are met:
+*
+* 1. Redistributions of source code must retain the above copyright notice,
+* this list of conditions and the following disclaimer.
+*
+* 2. Redistributions in binary form must reproduce the above copyright notice,
+* this list of conditions and the following disclaimer in the documentation
+* and/or other materials provided with the distribution.
+*
+* Subject to the terms and conditions of this license, each copyright holder
+* and contributor hereby grants to those receiving rights under this license
+* a perpetual, worldwide, non-exclusive, no-charge, royalty-free, irrevocable
+* (except for failure to satisfy the conditions of this license) patent license
+* to make, have made, use, offer to sell, sell, import, and otherwise transfer
+* this software, where such license applies only to those patent claims, already
+* acquired or hereafter acquired, licensable by such copyright holder or
+* contributor that are necessarily infringed by:
+*
+* (a) their Contribution(s) (the licensed copyrights of copyright holders and
+* non-copyrightable additions of contributors, in source or binary form) alone;
+* or
+*
+* (b) combination of their Contribution(s) with the work of authorship to which
+* such Contribution(s) was added by such copyright holder or contributor, if,
+* at the time the Contribution is added, such addition causes such combination
+* to be necessarily infringed. The patent license shall not apply to any other
+* combinations which include the Contribution.
+*
+* Except as expressly stated above, no rights or licenses from any copyright
+* holder or contributor is granted under this license, whether expressly, by
+* implication, estoppel or otherwise.
+*
+* DISCLAIMER
+*
+* THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+* AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+* IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+* DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS OR CONTRIBUTORS BE LIABLE
+* FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+* DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+* SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+* CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+* OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+* OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+*/
+
+use crate::{
+    exporter::{artifact::Artifact, plan::Plan},
+    project::Meta,
+};
+
+//================================================================
+
+use std::{
+    any::Any,
+    fmt::Display,
+    io::{BufRead, BufReader, Read},
+    os::unix::process::CommandExt,
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{Receiver, Sender, channel},
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+//================================================================
+
+pub type EventTx = Sender<Event>;
+pub type EventRx = Receiver<Event>;
+
+/// event sent by a step while it runs.
+pub enum Event {
+    /// the step has started.
+    Start,
+    /// the step has moved on to a sub-task.
+    Progress(Progress),
+    /// a line of output of the step's command.
+    Log(LogLine),
+    /// the step has produced an artifact.
+    Artifact(Artifact),
+    /// something went wrong, without failing the step.
+    Warning(String),
+    /// the step has finished, with its command's exit code if it has one.
+    Finish(anyhow::Result<()>, Option<i32>),
+}
+
+/// sub-task of a running step.
+#[derive(Clone)]
+pub struct Progress {
+    pub message: String,
+    /// how much of the step is done, from 0 to 1, if known.
+    pub fraction: Option<f32>,
+}
+
+/// line of output of a step's command.
+pub struct LogLine {
+    pub text: String,
+    /// whether the line was written to stderr.
+    pub stderr: bool,
+}
+
+/// sends the events of a step from the thread it runs on.
+#[derive(Clone)]
+pub struct Reporter {
+    tx: EventTx,
+}
+
+impl Reporter {
+    /// report that the step has moved on to a sub-task, with how much of the step is done, from 0 to 1.
+    pub fn progress(&self, message: &str, fraction: f32) {
+        let _ = self.tx.send(Event::Progress(Progress {
+            message: message.to_string(),
+            fraction: Some(fraction.clamp(0.0, 1.0)),
+        }));
+    }
+
+    /// report a problem that does not fail the step.
+    pub fn warning(&self, message: &str) {
+        let _ = self.tx.send(Event::Warning(message.to_string()));
+    }
+}
+
+/// task a step runs on its own thread before its command, e.g. staging files.
+pub type Task = Box<dyn FnOnce(&Reporter) -> anyhow::Result<()> + Send>;
+
+/// running state of a step.
+#[derive(Default)]
+pub struct EventHandler {
+    channel: Option<(EventTx, EventRx)>,
+    log: Vec<LogLine>,
+    progress: Vec<Progress>,
+    warning: Vec<String>,
+    produce: Vec<Artifact>,
+    child: Arc<Mutex<Option<Child>>>,
+    /// set once the step is stopped, so that a command not yet spawned is never run.
+    cancel: Arc<AtomicBool>,
+    start: Option<Instant>,
+    /// status to report once the command has been stopped.
+    stop: Option<ExportStatus>,
+    code: Option<i32>,
+    duration: Option<Duration>,
+}
+
+#[typetag::serde(tag = "type")]
+pub trait Export {
+    /// get the step as `Any`, for front-ends to downcast it to its exporter.
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn get_name(&self) -> &str;
+    fn get_enable(&self) -> bool;
+    fn get_remove(&self) -> bool;
+    fn get_status(&mut self) -> &mut ExportStatus;
+    fn get_handler(&mut self) -> &mut EventHandler;
+    fn get_path(&mut self) -> Vec<&mut String>;
+    fn get_need(&self) -> &[String];
+    fn get_timeout(&self) -> u64;
+    fn run(&mut self, meta: Meta) -> anyhow::Result<()>;
+
+    //================================================================
+
+    /// get the artifacts the step declares it will produce, without running it.
+    fn get_artifact(&self, _meta: &Meta) -> anyhow::Result<Vec<Artifact>> {
+        Ok(Vec::default())
+    }
+
+    /// get the files and folders the step's artifacts are built from.
+    fn get_source(&self, _meta: &Meta) -> anyhow::Result<Vec<PathBuf>> {
+        Ok(Vec::default())
+    }
+
+    /// check that the step can run, without running it, returning every problem found.
+    fn validate(&self, _meta: &Meta) -> Vec<String> {
+        Vec::default()
+    }
+
+    /// get the rank of the step in its stage. a step only runs once every step of a lower rank is
+    /// done, e.g. to use everything they produced.
+    fn get_rank(&self) -> u32 {
+        0
+    }
+
+    /// get the name of every step whose artifacts the step uses.
+    fn get_reference(&self) -> Vec<&str> {
+        Vec::default()
+    }
+
+    /// get everything the step would do when run, without touching the filesystem.
+    fn plan(&self, _meta: &Meta) -> anyhow::Result<Plan> {
+        Ok(Plan::default())
+    }
+
+    /// set the current status.
+    fn set_status(&mut self, status: ExportStatus) {
+        *self.get_status() = status;
+    }
+
+    /// execute command, in its own process group so the whole group can be stopped.
+    /// every artifact given is reported as produced once the command succeeds.
+    fn execute(&mut self, command: Command, artifact: Vec<Artifact>) {
+        self.execute_task(Box::new(|_| Ok(())), Some(command), artifact);
+    }
+
+    /// stage the files of a plan on the step's own thread, then execute its command as with
+    /// `execute`. a plan with nothing to do succeeds at once.
+    fn execute_plan(&mut self, mut plan: Plan, artifact: Vec<Artifact>) {
+        match plan.command.take() {
+            None if plan.action.is_empty() && plan.warning.is_empty() => {
+                self.set_status(ExportStatus::Success)
+            }
+            command => {
+                let folder: Vec<PathBuf> = artifact
+                    .iter()
+                    .filter_map(|x| x.path.parent().map(Path::to_path_buf))
+                    .collect();
+
+                let task: Task = Box::new(move |reporter| {
+                    plan.apply(reporter)?;
+
+                    // the command writes every artifact, but may not create its folder.
+                    for folder in folder {
+                        std::fs::create_dir_all(folder)?;
+                    }
+
+                    Ok(())
+                });
+
+                self.execute_task(task, command, artifact)
+            }
+        }
+    }
+
+    /// run a task on its own thread, then execute command, if any, as with `execute`.
+    fn execute_task(&mut self, task: Task, command: Option<Command>, artifact: Vec<Artifact>) {
+        let (tx, rx) = channel();
+        let handler = self.get_handler();
+
+        handler.channel = Some((tx.clone(), rx));
+        handler.log.clear();
+        handler.progress.clear();
+        handler.warning.clear();
+        handler.produce.clear();
+        handler.cancel = Arc::default();
+        handler.start = Some(Instant::now());
+        handler.stop = None;
+        handler.code = None;
+        handler.duration = None;
+
+        let shared = handler.child.clone();
+        let cancel = handler.cancel.clone();
+        let reporter = Reporter { tx: tx.clone() };
+
+        std::thread::spawn(move || {
+            let _ = tx.send(Event::Start);
+
+            if let Err(error) = task(&reporter) {
+                let _ = tx.send(Event::Finish(Err(error), None));
+                return;
+            }
+
+            let Some(mut command) = command else {
+                produce(artifact, &reporter);
+                let _ = tx.send(Event::Finish(Ok(()), None));
+                return;
+            };
+
+            command
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .process_group(0);
+
+            let program = command.get_program().display().to_string();
+            let _ = tx.send(Event::Progress(Progress {
+                message: format!("Running \"{program}\""),
+                fraction: None,
+            }));
+
+            let (stdout, stderr) = {
+                // hold the lock so that a stop can't slip in between the check and the spawn.
+                let mut lock = shared.lock().unwrap();
+
+                if cancel.load(Ordering::SeqCst) {
+                    let _ = tx.send(Event::Finish(Err(anyhow::Error::msg("Stopped.")), None));
+                    return;
+                }
+
+                match command.spawn() {
+                    Ok(mut child) => {
+                        let stdout = child.stdout.take();
+                        let stderr = child.stderr.take();
+                        *lock = Some(child);
+
+                        (stdout, stderr)
+                    }
+                    Err(error) => {
+                        let _ = tx.send(Event::Finish(
+                            Err(anyhow::Error::msg(format!(
+                                "Could not run \"{program}\": {error}."
+                            ))),
+                            None,
+                        ));
+                        return;
+                    }
+                }
+            };
+
+            let stdout = stream(stdout, false, tx.clone());
+            let stderr = stream(stderr, true, tx.clone());
+
+            // the child is kept in the handler while it runs, so it can be stopped, however long
+            // anything else holds its output open.
+            let status = loop {
+                if let Some(child) = shared.lock().unwrap().as_mut()
+                    && let Some(status) = child.try_wait().transpose()
+                {
+                    // whatever the child left behind in its group goes with it.
+                    kill_group(child.id());
+                    break status;
+                }
+
+                std::thread::sleep(Duration::from_millis(20));
+            };
+
+            *shared.lock().unwrap() = None;
+
+            // a process that left the group may still hold the output open, so it is only waited
+            // on for a little while.
+            let start = Instant::now();
+
+            while !(stdout.is_finished() && stderr.is_finished())
+                && start.elapsed() < Duration::from_secs(1)
+            {
+                std::thread::sleep(Duration::from_millis(20));
+            }
+
+            let stderr = if stderr.is_finished() {
+                stderr.join().unwrap_or_default()
+            } else {
+                String::default()
+            };
+
+            let code = status.as_ref().ok().and_then(|status| status.code());
+
+            let event = match status {
+                Ok(status) if status.success() => Ok(()),
+                Ok(status) if stderr.trim().is_empty() => Err(anyhow::Error::msg(format!(
+                    "Command exited unsuccessfully: {status}."
+                ))),
+                Ok(_) => Err(anyhow::Error::msg(stderr)),
+                Err(error) => Err(anyhow::Error::msg(error.to_string())),
+            };
+
+            if event.is_ok() {
+                produce(artifact, &reporter);
+            }
+
+            let _ = tx.send(Event::Finish(event, code));
+        });
+    }
+
+    /// forget everything the step reported in its last run.
+    fn clear(&mut self) {
+        let handler = self.get_handler();
+
+        handler.log.clear();
+        handler.progress.clear();
+        handler.warning.clear();
+        handler.produce.clear();
+        handler.code = None;
+        handler.duration = None;
+    }
+
+    /// stop the step, killing its whole process group if it is running.
+    fn stop(&mut self, status: ExportStatus) {
+        if *self.get_status() == ExportStatus::Waiting {
+            self.set_status(status);
+            return;
+        }
+
+        let handler = self.get_handler();
+
+        handler.cancel.store(true, Ordering::SeqCst);
+
+        if let Some(child) = handler.child.lock().unwrap().as_ref() {
+            kill_group(child.id());
+        }
+
+        handler.stop = Some(status);
+    }
+
+    /// poll for completion, stopping the step if it has run out of time.
+    fn poll_completion(&mut self) {
+        let timeout = self.get_timeout();
+        let handler = self.get_handler();
+
+        if timeout > 0
+            && handler.stop.is_none()
+            && handler
+                .start
+                .is_some_and(|start| start.elapsed() > Duration::from_secs(timeout))
+        {
+            self.stop(ExportStatus::TimedOut);
+        }
+
+        let handler = self.get_handler();
+        let mut finish = None;
+
+        if let Some((_, rx)) = &handler.channel {
+            while let Ok(event) = rx.try_recv() {
+                match event {
+                    Event::Start => handler.progress.push(Progress {
+                        message: "Started".to_string(),
+                        fraction: Some(0.0),
+                    }),
+                    Event::Progress(progress) => handler.progress.push(progress),
+                    Event::Log(line) => handler.log.push(line),
+                    Event::Artifact(artifact) => handler.produce.push(artifact),
+                    Event::Warning(warning) => handler.warning.push(warning),
+                    Event::Finish(result, code) => {
+                        handler.code = code;
+                        finish = Some(result);
+                    }
+                }
+            }
+        }
+
+        if let Some(result) = finish {
+            let status = match (handler.stop.take(), result) {
+                (Some(status), _) => status,
+                (None, Ok(_)) => ExportStatus::Success,
+                (None, Err(error)) => ExportStatus::Failure(error.to_string()),
+            };
+
+            handler.duration = handler.start.take().map(|start| start.elapsed());
+
+            self.set_status(status);
+        }
+    }
+
+    /// get the output of the step's last command.
+    fn get_log(&mut self) -> &[LogLine] {
+        &self.get_handler().log
+    }
+
+    /// get every sub-task the step has moved on to, the current one last.
+    fn get_progress(&mut self) -> &[Progress] {
+        &self.get_handler().progress
+    }
+
+    /// get every warning the step has reported.
+    fn get_warning(&mut self) -> &[String] {
+        &self.get_handler().warning
+    }
+
+    /// get every artifact the step has produced.
+    fn get_produce(&mut self) -> &[Artifact] {
+        &self.get_handler().produce
+    }
+
+    /// get the exit code of the step's last command, if it has exited with one.
+    fn get_code(&mut self) -> Option<i32> {
+        self.get_handler().code
+    }
+
+    /// get how long the step's last command ran for.
+    fn get_duration(&mut self) -> Option<Duration> {
+        self.get_handler().duration
+    }
+
+    /// check if the exporter is no longer waiting or in progress.
+    fn success_or_failure(&mut self) -> bool {
+        !matches!(
+            self.get_status(),
+            ExportStatus::Waiting | ExportStatus::InProgress
+        )
+    }
+}
+
+/// report every artifact that exists as produced, and warn about every one that doesn't.
+fn produce(artifact: Vec<Artifact>, reporter: &Reporter) {
+    for artifact in artifact {
+        if artifact.path.exists() {
+            let _ = reporter.tx.send(Event::Artifact(artifact));
+        } else {
+            reporter.warning(&format!(
+                "Artifact \"{}\" was not produced.",
+                artifact.path.display()
+            ));
+        }
+    }
+}
+
+/// kill every process in the group a child leads.
+fn kill_group(id: u32) {
+    // the child leads its own process group, so its id is also the group's id.
+    unsafe {
+        libc::kill(-(id as i32), libc::SIGKILL);
+    }
+}
+
+/// send every line read from a command's output through the event channel, as it comes.
+/// returns the whole output once the command closes it.
+fn stream<R: Read + Send + 'static>(
+    reader: Option<R>,
+    stderr: bool,
+    tx: EventTx,
+) -> JoinHandle<String> {
+    std::thread::spawn(move || {
+        let mut result = String::new();
+        let Some(reader) = reader else {
+            return result;
+        };
+
+        let mut reader = BufReader::new(reader);
+        let mut line = Vec::new();
+
+        while let Ok(size) = reader.read_until(b'\n', &mut line)
+            && size > 0
+        {
+            let text = String::from_utf8_lossy(&line).trim_end().to_string();
+
+            result.push_str(&text);
+            result.push('\n');
+
+            let _ = tx.send(Event::Log(LogLine { text, stderr }));
+
+            line.clear();
+        }
+
+        result
+    })
+}
+
+/// get the kind of a step, as written in the project file.
+pub fn kind(step: &dyn Export) -> String {
+    serde_json::to_value(step)
+        .ok()
+        .and_then(|value| value["type"].as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// get the label a step is reported by: its name, or its kind if it has none.
+pub fn label(step: &dyn Export) -> String {
+    if step.get_name().is_empty() {
+        kind(step)
+    } else {
+        step.get_name().to_string()
+    }
+}
+
+/// check that the file at a path from the project file exists, if the path is set.
+pub fn validate_file(meta: &Meta, path: &str, label: &str) -> Option<String> {
+    match meta.template.format(path) {
+        Ok(path) if path.is_empty() || meta.resolve(&path).is_file() => None,
+        Ok(path) => Some(format!("{label} \"{path}\" does not exist.")),
+        Err(error) => Some(error.to_string()),
+    }
+}
+
+/// check that a tool can be found, either as a path or in PATH.
+pub fn validate_tool(tool: &str) -> Option<String> {
+    let exist = if tool.contains(std::path::MAIN_SEPARATOR) {
+        Path::new(tool).is_file()
+    } else {
+        std::env::var_os("PATH").is_some_and(|path| {
+            std::env::split_paths(&path).any(|folder| folder.join(tool).is_file())
+        })
+    };
+
+    if exist {
+        None
+    } else {
+        Some(format!("Tool \"{tool}\" was not found, install it first."))
+    }
+}
+
+pub fn format_name(text: &str, name: &str) -> String {
+    if name.is_empty() {
+        text.to_string()
+    } else {
+        format!("{text} - ({name})")
+    }
+}
+
+//================================================================
+
+#[derive(Default, PartialEq, Eq)]
+pub enum ExportStatus {
+    #[default]
+    InProgress,
+    Waiting,
+    Success,
+    Failure(String),
+    Skipped,
+    Cancelled,
+    TimedOut,
+}
+
+impl Display for ExportStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportStatus::InProgress => f.write_str("In Progress"),
+            ExportStatus::Waiting => f.write_str("Waiting"),
+            ExportStatus::Success => f.write_str("Success"),
+            ExportStatus::Failure(error) => f.write_str(&format!("Failure: {error}")),
+            ExportStatus::Skipped => f.write_str("Skipped"),
+            ExportStatus::Cancelled => f.write_str("Cancelled"),
+            ExportStatus::TimedOut => f.write_str("Timed Out"),
+        }
+    }
+}
+
+//================================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exporter::script::Script;
+    use std::{os::unix::fs::PermissionsExt, path::Path};
+
+    /// check if a process is gone, or only left as a zombie.
+    fn dead(pid: &str) -> bool {
+        std::fs::read_to_string(format!("/proc/{pid}/stat"))
+            .map(|x| x.split(' ').nth(2) == Some("Z"))
+            .unwrap_or(true)
+    }
+
+    /// run a script that leaves a child of its own behind, stopping it either by hand or by timeout.
+    fn stop(path: &Path, timeout: u64) -> ExportStatus {
+        std::fs::create_dir_all(path).unwrap();
+
+        let script = path.join("step.sh");
+        std::fs::write(
+            &script,
+            "#!/bin/sh\nsleep 30 &\necho $! > child.pid\nwait\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut step = Script::default();
+        step.script = script.display().to_string();
+        step.timeout = timeout;
+        step.run(Meta {
+            path: path.to_path_buf(),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let start = Instant::now();
+        let pid = path.join("child.pid");
+
+        // the child holds the output open, so the step can only finish once the child is killed, too.
+        while !step.success_or_failure() {
+            assert!(
+                start.elapsed() < Duration::from_secs(10),
+                "step was not stopped"
+            );
+
+            if timeout == 0 && pid.is_file() {
+                step.stop(ExportStatus::Cancelled);
+            }
+
+            step.poll_completion();
+            std::thread::sleep(Duration::from_millis(20));
+        }
+
+        let pid = std::fs::read_to_string(&pid).unwrap();
+        assert!(dead(pid.trim()), "child of the step is still running");
+
+        std::fs::remove_dir_all(path).unwrap();
+
+        std::mem::take(step.get_status())
+    }
+
+    #[test]
+    fn exit_group() {
+        let path = std::env::temp_dir().join(format!("boondle_exit_{}", std::process::id()));
+        std::fs::create_dir_all(&path).unwrap();
+
+        // one child stays in the step's group, the other leaves it, both holding the output open.
+        let script = path.join("step.sh");
+        std::fs::write(
+            &script,
+            "#!/bin/sh\nsleep 30 &\necho $! > child.pid\nsetsid sleep 5 &\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut step = Script::default();
+        step.script = script.display().to_string();
+        step.run(Meta {
+            path: path.clone(),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let start = Instant::now();
+
+        // the step is done once the script exits, not once its output is closed.
+        while !step.success_or_failure() {
+            assert!(
+                start.elapsed() < Duration::from_secs(4),
+                "step did not finish"
+            );
+
+            step.poll_completion();
+            std::thread::sleep(Duration::from_millis(20));
+        }
+
+        assert!(*step.get_status() == ExportStatus::Success);
+
+        let pid = std::fs::read_to_string(path.join("child.pid")).unwrap();
+        assert!(dead(pid.trim()), "child of the step is still running");
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+
+    #[test]
+    fn stop_group() {
+        let path = std::env::temp_dir().join(format!("boondle_stop_{}", std::process::id()));
+
+        assert!(stop(&path.join("cancel"), 0) == ExportStatus::Cancelled);
+        assert!(stop(&path.join("timeout"), 1) == ExportStatus::TimedOut);
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+}
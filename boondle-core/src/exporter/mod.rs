@@ -0,0 +1,9 @@
+pub mod app_image;
+pub mod artifact;
+pub mod cargo;
+pub mod checksum;
+pub mod debian;
+pub mod export;
+pub mod plan;
+pub mod script;
+pub mod sign;
@@ -0,0 +1,400 @@
+/*
+* Copyright (c) 2025 luxreduxdelux
+*
+* Redistribution and use in source and binary forms, with or without
+* modification, are permitted provided that the following conditions are met:
+*
+* 1. Redistributions of source code must retain the above copyright notice,
+* this list of conditions and the following disclaimer.
+*
+* 2. Redistributions in binary form must reproduce the above copyright notice,
+* this list of conditions and the following disclaimer in the documentation
+* and/or other materials provided with the distribution.
+*
+* Subject to the terms and conditions of this license, each copyright holder
+* and contributor hereby grants to those receiving rights under this license
+* a perpetual, worldwide, non-exclusive, no-charge, royalty-free, irrevocable
+* (except for failure to satisfy the conditions of this license) patent license
+* to make, have made, use, offer to sell, sell, import, and otherwise transfer
+* this software, where such license applies only to those patent claims, already
+* acquired or hereafter acquired, licensable by such copyright holder or
+* contributor that are necessarily infringed by:
+*
+* (a) their Contribution(s) (the licensed copyrights of copyright holders and
+* non-copyrightable additions of contributors, in source or binary form) alone;
+* or
+*
+* (b) combination of their Contribution(s) with the work of authorship to which
+* such Contribution(s) was added by such copyright holder or contributor, if,
+* at the time the Contribution is added, such addition causes such combination
+* to be necessarily infringed. The patent license shall not apply to any other
+* combinations which include the Contribution.
+*
+* Except as expressly stated above, no rights or licenses from any copyright
+* holder or contributor is granted under this license, whether expressly, by
+* implication, estoppel or otherwise.
+*
+* DISCLAIMER
+*
+* THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+* AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+* IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+* DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS OR CONTRIBUTORS BE LIABLE
+* FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+* DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+* SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+* CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+* OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+* OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+*/
+
+use crate::exporter::{checksum::Hash, export::Reporter};
+
+//================================================================
+
+use std::{
+    ffi::OsStr,
+    fmt::Display,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, SystemTime},
+};
+
+//================================================================
+
+/// a file a step stages into its work folder.
+pub enum Action {
+    /// copy a file.
+    Copy {
+        source: PathBuf,
+        /// path in the work folder.
+        destination: PathBuf,
+        mode: u32,
+    },
+    /// write a generated file.
+    Write {
+        /// path in the work folder.
+        destination: PathBuf,
+        data: String,
+        mode: u32,
+    },
+    /// write a checksum manifest of every source file, hashed once the step runs.
+    Hash {
+        source: Vec<PathBuf>,
+        /// path in the work folder.
+        destination: PathBuf,
+        hash: Hash,
+        mode: u32,
+    },
+}
+
+/// everything a step does when run, resolved before anything is touched. it is both what a step
+/// runs and what a dry run shows.
+#[derive(Default)]
+pub struct Plan {
+    /// step name.
+    pub step: String,
+    /// step exporter kind.
+    pub kind: String,
+    /// folder every file is staged into.
+    pub folder: Option<PathBuf>,
+    pub action: Vec<Action>,
+    /// command run once every file is staged.
+    pub command: Option<Command>,
+    /// problems that do not fail the step.
+    pub warning: Vec<String>,
+    /// SOURCE_DATE_EPOCH to date every staged file and folder to, for a reproducible package.
+    pub epoch: Option<u64>,
+}
+
+impl Plan {
+    pub fn new(folder: Option<PathBuf>, command: Option<Command>) -> Self {
+        Self {
+            folder,
+            command,
+            ..Default::default()
+        }
+    }
+
+    /// stage a copy of a file.
+    pub fn copy(&mut self, source: impl Into<PathBuf>, destination: impl Into<PathBuf>, mode: u32) {
+        self.action.push(Action::Copy {
+            source: source.into(),
+            destination: destination.into(),
+            mode,
+        });
+    }
+
+    /// stage a generated file.
+    pub fn write(&mut self, destination: impl Into<PathBuf>, data: String, mode: u32) {
+        self.action.push(Action::Write {
+            destination: destination.into(),
+            data,
+            mode,
+        });
+    }
+
+    /// stage a checksum manifest of every source file.
+    pub fn hash(&mut self, source: Vec<PathBuf>, hash: Hash, mode: u32) {
+        self.action.push(Action::Hash {
+            source,
+            destination: PathBuf::from(hash.file()),
+            hash,
+            mode,
+        });
+    }
+
+    /// stage every file into the work folder, reporting each one.
+    pub fn apply(&self, reporter: &Reporter) -> anyhow::Result<()> {
+        for warning in &self.warning {
+            reporter.warning(warning);
+        }
+
+        let Some(folder) = &self.folder else {
+            return Ok(());
+        };
+
+        std::fs::create_dir_all(folder)?;
+
+        for (i, action) in self.action.iter().enumerate() {
+            let fraction = 0.1 + 0.6 * i as f32 / self.action.len() as f32;
+
+            let (destination, mode) = match action {
+                Action::Copy {
+                    source,
+                    destination,
+                    mode,
+                } => {
+                    reporter.progress(&format!("Copying {}", destination.display()), fraction);
+
+                    let path = Self::create(folder, destination)?;
+                    std::fs::copy(source, &path)?;
+
+                    (path, mode)
+                }
+                Action::Write {
+                    destination,
+                    data,
+                    mode,
+                } => {
+                    reporter.progress(&format!("Writing {}", destination.display()), fraction);
+
+                    let path = Self::create(folder, destination)?;
+                    std::fs::write(&path, data)?;
+
+                    (path, mode)
+                }
+                Action::Hash {
+                    source,
+                    destination,
+                    hash,
+                    mode,
+                } => {
+                    reporter.progress(&format!("Hashing {}", destination.display()), fraction);
+
+                    let path = Self::create(folder, destination)?;
+                    std::fs::write(&path, hash.manifest(folder, source)?)?;
+
+                    (path, mode)
+                }
+            };
+
+            std::fs::set_permissions(destination, std::fs::Permissions::from_mode(*mode))?;
+        }
+
+        if let Some(epoch) = self.epoch {
+            reporter.progress(&format!("Dating files to {epoch}"), 0.7);
+
+            Self::normalize(folder, SystemTime::UNIX_EPOCH + Duration::from_secs(epoch))?;
+        }
+
+        Ok(())
+    }
+
+    /// date every file and folder in a staged tree to a time, with every folder's mode set, so that
+    /// the tree doesn't depend on when it was staged, or with which umask.
+    fn normalize(path: &Path, time: SystemTime) -> anyhow::Result<()> {
+        let metadata = std::fs::symlink_metadata(path)?;
+
+        if metadata.is_symlink() {
+            return Ok(());
+        }
+
+        if metadata.is_dir() {
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))?;
+
+            for entry in std::fs::read_dir(path)? {
+                Self::normalize(&entry?.path(), time)?;
+            }
+        }
+
+        std::fs::File::open(path)?.set_modified(time)?;
+
+        Ok(())
+    }
+
+    /// get the full path of a file to stage, creating its parent folder.
+    fn create(folder: &Path, destination: &Path) -> anyhow::Result<PathBuf> {
+        let path = folder.join(destination);
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        Ok(path)
+    }
+
+    /// quote a command-line argument, if it needs to be.
+    fn quote(text: &OsStr) -> String {
+        let text = text.to_string_lossy();
+
+        if text.is_empty() || text.contains(char::is_whitespace) {
+            format!("{text:?}")
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+impl Display for Plan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(folder) = &self.folder {
+            writeln!(f, "Folder: {}", folder.display())?;
+        }
+
+        if let Some(epoch) = self.epoch {
+            writeln!(f, "Date: {epoch}")?;
+        }
+
+        for action in &self.action {
+            match action {
+                Action::Copy {
+                    source,
+                    destination,
+                    mode,
+                } => writeln!(
+                    f,
+                    "Copy: {} -> {} ({mode:04o})",
+                    source.display(),
+                    destination.display()
+                )?,
+                Action::Write {
+                    destination,
+                    data,
+                    mode,
+                } => {
+                    writeln!(f, "Write: {} ({mode:04o})", destination.display())?;
+
+                    for line in data.lines() {
+                        writeln!(f, "    | {line}")?;
+                    }
+                }
+                Action::Hash {
+                    source,
+                    destination,
+                    mode,
+                    ..
+                } => {
+                    writeln!(f, "Hash: {} ({mode:04o})", destination.display())?;
+
+                    for path in source {
+                        writeln!(f, "    < {}", path.display())?;
+                    }
+                }
+            }
+        }
+
+        if let Some(command) = &self.command {
+            let mut line = Self::quote(command.get_program());
+
+            for argument in command.get_args() {
+                line.push(' ');
+                line.push_str(&Self::quote(argument));
+            }
+
+            writeln!(f, "Run: {line}")?;
+
+            if let Some(folder) = command.get_current_dir() {
+                writeln!(f, "    In: {}", folder.display())?;
+            }
+
+            for (key, value) in command.get_envs() {
+                let key = key.to_string_lossy();
+
+                // a dry run may end up in a shared log, so a passphrase is never shown.
+                let value = if key.contains("PASSPHRASE") {
+                    "***".to_string()
+                } else {
+                    Self::quote(value.unwrap_or_default())
+                };
+
+                writeln!(f, "    Env: {key}={value}")?;
+            }
+        }
+
+        for warning in &self.warning {
+            writeln!(f, "Warning: {warning}")?;
+        }
+
+        Ok(())
+    }
+}
+
+//================================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let mut command = Command::new("dpkg-deb");
+        command.arg("--build").arg("my folder");
+        command.env("KEY", "value");
+
+        let mut plan = Plan::new(Some(PathBuf::from("/work")), Some(command));
+        plan.write("DEBIAN/control", "Package: app\n".to_string(), 0o644);
+        plan.copy("/target/app", "usr/bin/app", 0o755);
+        plan.warning.push("No icon.".to_string());
+
+        assert_eq!(
+            plan.to_string(),
+            "Folder: /work
+Write: DEBIAN/control (0644)
+    | Package: app
+Copy: /target/app -> usr/bin/app (0755)
+Run: dpkg-deb --build \"my folder\"
+    Env: KEY=value
+Warning: No icon.
+"
+        );
+    }
+
+    #[test]
+    fn normalize() {
+        let path = std::env::temp_dir().join(format!("boondle_normalize_{}", std::process::id()));
+        std::fs::create_dir_all(path.join("usr/bin")).unwrap();
+        std::fs::write(path.join("usr/bin/app"), "app").unwrap();
+        std::fs::set_permissions(path.join("usr"), std::fs::Permissions::from_mode(0o700)).unwrap();
+        std::os::unix::fs::symlink("missing", path.join("usr/link")).unwrap();
+
+        let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        Plan::normalize(&path, time).unwrap();
+
+        for file in ["", "usr", "usr/bin", "usr/bin/app"] {
+            let metadata = std::fs::metadata(path.join(file)).unwrap();
+            assert_eq!(metadata.modified().unwrap(), time, "{file}");
+
+            if metadata.is_dir() {
+                assert_eq!(metadata.permissions().mode() & 0o7777, 0o755, "{file}");
+            }
+        }
+
+        // a link is left as is, even one that points nowhere.
+        let link = std::fs::symlink_metadata(path.join("usr/link")).unwrap();
+        assert_ne!(link.modified().unwrap(), time);
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+}
@@ -0,0 +1,332 @@
+/*
+* Copyright (c) 2025 luxreduxdelux
+*
+* Redistribution and use in source and binary forms, with or without
+* modification, are permitted provided that the following conditions are met:
+*
+* 1. Redistributions of source code must retain the above copyright notice,
+* this list of conditions and the following disclaimer.
+*
+* 2. Redistributions in binary form must reproduce the above copyright notice,
+* this list of conditions and the following disclaimer in the documentation
+* and/or other materials provided with the distribution.
+*
+* Subject to the terms and conditions of this license, each copyright holder
+* and contributor hereby grants to those receiving rights under this license
+* a perpetual, worldwide, non-exclusive, no-charge, royalty-free, irrevocable
+* (except for failure to satisfy the conditions of this license) patent license
+* to make, have made, use, offer to sell, sell, import, and otherwise transfer
+* this software, where such license applies only to those patent claims, already
+* acquired or hereafter acquired, licensable by such copyright holder or
+* contributor that are necessarily infringed by:
+*
+* (a) their Contribution(s) (the licensed copyrights of copyright holders and
+* non-copyrightable additions of contributors, in source or binary form) alone;
+* or
+*
+* (b) combination of their Contribution(s) with the work of authorship to which
+* such Contribution(s) was added by such copyright holder or contributor, if,
+* at the time the Contribution is added, such addition causes such combination
+* to be necessarily infringed. The patent license shall not apply to any other
+* combinations which include the Contribution.
+*
+* Except as expressly stated above, no rights or licenses from any copyright
+* holder or contributor is granted under this license, whether expressly, by
+* implication, estoppel or otherwise.
+*
+* DISCLAIMER
+*
+* THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+* AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+* IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+* DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS OR CONTRIBUTORS BE LIABLE
+* FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+* DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+* SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+* CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+* OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+* OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+*/
+
+use crate::{
+    exporter::{
+        artifact::{Artifact, ArtifactKind},
+        export::*,
+        plan::Plan,
+    },
+    project::Meta,
+};
+
+//================================================================
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+
+//================================================================
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind")]
+pub enum Layout {
+    String {
+        name: String,
+        data: String,
+        #[serde(skip)]
+        remove: bool,
+    },
+    Integer {
+        name: String,
+        data: i64,
+        #[serde(skip)]
+        remove: bool,
+    },
+    Decimal {
+        name: String,
+        data: f64,
+        #[serde(skip)]
+        remove: bool,
+    },
+    Boolean {
+        name: String,
+        data: bool,
+        #[serde(skip)]
+        remove: bool,
+    },
+}
+
+/// file a script writes.
+#[derive(Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Output {
+    /// Path to the file, relative to the project folder.
+    pub path: String,
+    /// Kind of the file: a Binary for a package step to take with binary_from, or a Package for
+    /// checksum and sign steps to pick up.
+    #[serde(default)]
+    pub kind: ArtifactKind,
+    /// Architecture of the file, e.g. "amd64". Empty for the host's architecture for a binary, and
+    /// for "all" otherwise.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub architecture: String,
+    #[serde(skip)]
+    pub remove: bool,
+}
+
+impl Output {
+    /// get the architecture of the file, falling back to the host's for a binary.
+    pub fn architecture(&self) -> &str {
+        match (self.architecture.is_empty(), self.kind) {
+            (false, _) => &self.architecture,
+            (true, ArtifactKind::Binary) => std::env::consts::ARCH,
+            (true, _) => "all",
+        }
+    }
+}
+
+#[derive(Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Script {
+    /// Exporter name, to tell apart exporters of the same kind.
+    pub name: String,
+    /// Path to the script to run.
+    pub script: String,
+    /// Variables given to the script as environment variables.
+    pub layout: Vec<Layout>,
+    /// Files the script writes, as artifacts for later steps.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub output: Vec<Output>,
+    /// Names of steps that must succeed before this one is run.
+    #[serde(default)]
+    pub need: Vec<String>,
+    /// Seconds the step may run before it is stopped. 0 for no limit.
+    #[serde(default)]
+    pub timeout: u64,
+    /// Whether the exporter is run.
+    pub enable: bool,
+    #[serde(skip)]
+    pub remove: bool,
+    #[serde(skip)]
+    status: ExportStatus,
+    #[serde(skip)]
+    handler: EventHandler,
+}
+
+#[typetag::serde]
+impl Export for Script {
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    fn get_enable(&self) -> bool {
+        self.enable
+    }
+
+    fn get_remove(&self) -> bool {
+        self.remove
+    }
+
+    fn get_status(&mut self) -> &mut ExportStatus {
+        &mut self.status
+    }
+
+    fn get_handler(&mut self) -> &mut EventHandler {
+        &mut self.handler
+    }
+
+    fn get_path(&mut self) -> Vec<&mut String> {
+        let mut list = vec![&mut self.script];
+        list.extend(self.output.iter_mut().map(|x| &mut x.path));
+        list
+    }
+
+    fn get_need(&self) -> &[String] {
+        &self.need
+    }
+
+    fn get_timeout(&self) -> u64 {
+        self.timeout
+    }
+
+    fn get_artifact(&self, meta: &Meta) -> anyhow::Result<Vec<Artifact>> {
+        self.output
+            .iter()
+            .map(|output| {
+                Ok(Artifact::new(
+                    &self.name,
+                    meta.resolve(&meta.template.format(&output.path)?),
+                    output.kind,
+                    output.architecture(),
+                ))
+            })
+            .collect()
+    }
+
+    fn validate(&self, meta: &Meta) -> Vec<String> {
+        let mut problem = Vec::new();
+
+        if meta.name.is_empty() {
+            problem.push("Project name cannot be empty.".to_string());
+        }
+
+        if meta.version.is_empty() {
+            problem.push("Project version cannot be empty.".to_string());
+        }
+
+        problem.extend(validate_file(meta, &self.script, "Script"));
+
+        for output in &self.output {
+            if let Err(error) = meta.template.format(&output.path) {
+                problem.push(error.to_string());
+            }
+        }
+
+        for widget in &self.layout {
+            if let Layout::String { data, .. } = widget
+                && let Err(error) = meta.template.format(data)
+            {
+                problem.push(error.to_string());
+            }
+        }
+
+        problem
+    }
+
+    fn plan(&self, meta: &Meta) -> anyhow::Result<Plan> {
+        let script = meta.template.format(&self.script)?;
+
+        if script.is_empty() {
+            return Ok(Plan::default());
+        }
+
+        let mut command = meta.command(meta.resolve(&script));
+        command.current_dir(&meta.path);
+
+        // TO-DO move into own function?
+        command.env("BOONDLE_NAME", &meta.name);
+        command.env("BOONDLE_ICON", &meta.icon);
+        command.env("BOONDLE_INFO", &meta.info);
+        command.env("BOONDLE_FROM", &meta.from);
+        command.env("BOONDLE_VERSION", &meta.version);
+        command.env("BOONDLE_NAME_GENERIC", &meta.name_generic);
+        command.env("BOONDLE_NAME_COMMENT", &meta.comment);
+        command.env("BOONDLE_NAME_CATEGORY", &meta.category);
+        command.env("BOONDLE_NAME_KEY_WORD", &meta.key_word);
+        command.env("BOONDLE_HOMEPAGE", &meta.homepage);
+        command.env("BOONDLE_LICENSE", &meta.license);
+
+        for widget in &self.layout {
+            match widget {
+                Layout::String { name, data, .. } => {
+                    command.env(name.to_uppercase(), meta.template.format(data)?)
+                }
+                Layout::Integer { name, data, .. } => {
+                    command.env(name.to_uppercase(), data.to_string())
+                }
+                Layout::Decimal { name, data, .. } => {
+                    command.env(name.to_uppercase(), data.to_string())
+                }
+                Layout::Boolean { name, data, .. } => {
+                    command.env(name.to_uppercase(), if *data { "1" } else { "0" })
+                }
+            };
+        }
+
+        Ok(Plan::new(None, Some(command)))
+    }
+
+    fn run(&mut self, meta: Meta) -> anyhow::Result<()> {
+        self.status = ExportStatus::InProgress;
+
+        let plan = self.plan(&meta)?;
+        let artifact = self.get_artifact(&meta)?;
+
+        self.execute_plan(plan, artifact);
+
+        Ok(())
+    }
+}
+
+//================================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exporter::{checksum::Checksum, plan::Action};
+    use std::path::PathBuf;
+
+    #[test]
+    fn output() {
+        let mut meta = Meta {
+            path: "/project".into(),
+            ..Default::default()
+        };
+        meta.template = meta.template.with("name", "app");
+
+        let script = Script {
+            name: "tarball".to_string(),
+            output: vec![Output {
+                path: "dist/{name}.tar.gz".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let artifact = script.get_artifact(&meta).unwrap();
+        assert_eq!(artifact.len(), 1);
+        assert_eq!(artifact[0].path, PathBuf::from("/project/dist/app.tar.gz"));
+        assert_eq!(artifact[0].kind, ArtifactKind::Package);
+
+        // a checksum step run after the script hashes what it declared.
+        meta.artifact = artifact;
+
+        let plan = Checksum::default().plan(&meta).unwrap();
+        assert!(plan.action.iter().any(|action| matches!(
+            action,
+            Action::Hash { source, .. } if source == &vec![PathBuf::from("/project/dist/app.tar.gz")]
+        )));
+    }
+}
@@ -0,0 +1,620 @@
+/*
+* Copyright (c) 2025 luxreduxdelux
+*
+* Redistribution and use in source and binary forms, with or without
+* modification, are permitted provided that the following conditions are met:
+*
+* 1. Redistributions of source code must retain the above copyright notice,
+* this list of conditions and the following disclaimer.
+*
+* 2. Redistributions in binary form must reproduce the above copyright notice,
+* this list of conditions and the following disclaimer in the documentation
+* and/or other materials provided with the distribution.
+*
+* Subject to the terms and conditions of this license, each copyright holder
+* and contributor hereby grants to those receiving rights under this license
+* a perpetual, worldwide, non-exclusive, no-charge, royalty-free, irrevocable
+* (except for failure to satisfy the conditions of this license) patent license
+* to make, have made, use, offer to sell, sell, import, and otherwise transfer
+* this software, where such license applies only to those patent claims, already
+* acquired or hereafter acquired, licensable by such copyright holder or
+* contributor that are necessarily infringed by:
+*
+* (a) their Contribution(s) (the licensed copyrights of copyright holders and
+* non-copyrightable additions of contributors, in source or binary form) alone;
+* or
+*
+* (b) combination of their Contribution(s) with the work of authorship to which
+* such Contribution(s) was added by such copyright holder or contributor, if,
+* at the time the Contribution is added, such addition causes such combination
+* to be necessarily infringed. The patent license shall not apply to any other
+* combinations which include the Contribution.
+*
+* Except as expressly stated above, no rights or licenses from any copyright
+* holder or contributor is granted under this license, whether expressly, by
+* implication, estoppel or otherwise.
+*
+* DISCLAIMER
+*
+* THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+* AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+* IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+* DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS OR CONTRIBUTORS BE LIABLE
+* FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+* DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+* SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+* CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+* OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+* OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+*/
+
+use crate::{
+    exporter::{
+        artifact::{Artifact, ArtifactKind},
+        export::*,
+        plan::Plan,
+    },
+    project::Meta,
+};
+
+//================================================================
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::{
+    any::Any,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+//================================================================
+
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum SignTool {
+    #[default]
+    Gpg,
+    Minisign,
+}
+
+impl SignTool {
+    pub const LIST: [Self; 2] = [Self::Gpg, Self::Minisign];
+
+    /// get the extension of a detached signature.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Gpg => "asc",
+            Self::Minisign => "sig",
+        }
+    }
+}
+
+impl Display for SignTool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SignTool::Gpg => f.write_str("GPG"),
+            SignTool::Minisign => f.write_str("minisign"),
+        }
+    }
+}
+
+/// key to sign packages with, shared by the sign step and by every step that embeds a signature.
+#[derive(Default, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Signing {
+    /// Tool to sign with.
+    #[serde(default)]
+    pub tool: SignTool,
+    /// GPG key ID or user ID to sign with, from your own keyring.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub key: String,
+    /// Path to a key file to sign with: an exported GPG secret key, imported into a keyring of
+    /// its own, or a minisign secret key.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub key_file: String,
+    /// Name of the environment variable holding the key's passphrase. Empty for a key with none.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub passphrase: String,
+}
+
+impl Signing {
+    /// check if no key is set, in which case nothing is signed.
+    pub fn is_empty(&self) -> bool {
+        self.key.is_empty() && self.key_file.is_empty()
+    }
+
+    /// get the key's passphrase, from its environment variable.
+    pub fn passphrase_value(&self) -> Option<String> {
+        if self.passphrase.is_empty() {
+            None
+        } else {
+            std::env::var(&self.passphrase).ok()
+        }
+    }
+
+    /// check that packages can be signed, returning every problem found.
+    pub fn validate(&self, meta: &Meta) -> Vec<String> {
+        let mut problem = Vec::new();
+
+        if self.is_empty() {
+            problem.push("No signing key is set in the project.".to_string());
+        }
+
+        if self.tool == SignTool::Minisign && self.key_file.is_empty() {
+            problem.push("Signing with minisign needs a key file.".to_string());
+        }
+
+        problem.extend(validate_file(meta, &self.key_file, "Key file"));
+
+        let tool = match self.tool {
+            SignTool::Gpg => "gpg",
+            SignTool::Minisign => "minisign",
+        };
+
+        problem.extend(validate_tool(tool));
+
+        if !self.passphrase.is_empty() {
+            if !Self::variable_name(&self.passphrase) {
+                problem.push(format!(
+                    "Passphrase variable \"{}\" is not a valid name.",
+                    self.passphrase
+                ));
+            } else if self.passphrase_value().is_none() {
+                problem.push(format!(
+                    "Passphrase variable \"{}\" is not set.",
+                    self.passphrase
+                ));
+            }
+        }
+
+        problem
+    }
+
+    /// get the shell script that signs every file with a detached signature. with GPG, every
+    /// Debian package gets a signature embedded in the format `debsigs` writes, too.
+    pub fn script(&self, list: &[PathBuf]) -> anyhow::Result<String> {
+        if !self.passphrase.is_empty() && !Self::variable_name(&self.passphrase) {
+            return Err(anyhow::Error::msg(format!(
+                "Sign: Passphrase variable \"{}\" is not a valid name.",
+                self.passphrase
+            )));
+        }
+
+        let mut script = String::from("#!/bin/sh\n# sign every package of the run.\nset -e\n\n");
+
+        // the passphrase is only ever read from the environment, never written down.
+        let feed = if self.passphrase.is_empty() {
+            String::default()
+        } else {
+            format!("printf '%s\\n' \"${{{}}}\" | ", self.passphrase)
+        };
+
+        match self.tool {
+            SignTool::Gpg => {
+                let mut option = "--batch --yes".to_string();
+
+                if !self.passphrase.is_empty() {
+                    option.push_str(" --pinentry-mode loopback --passphrase-fd 0");
+                }
+
+                if !self.key_file.is_empty() {
+                    // the keyring holds the secret key, so it lives outside the staging folder and
+                    // is removed once the script exits, however it exits.
+                    script.push_str("# a keyring of its own, with only the key file in it.\n");
+                    script.push_str("export GNUPGHOME=\"$(mktemp -d)\"\n");
+                    script.push_str("chmod 700 \"$GNUPGHOME\"\n");
+                    script
+                        .push_str("trap 'gpgconf --kill gpg-agent; rm -rf \"$GNUPGHOME\"' EXIT\n");
+                    script.push_str(&format!(
+                        "{feed}gpg {option} --import {}\n\n",
+                        Self::quote(&self.key_file)
+                    ));
+                }
+
+                if !self.key.is_empty() {
+                    option.push_str(&format!(" --local-user {}", Self::quote(&self.key)));
+                }
+
+                script.push_str(&format!("sign() {{\n    {feed}gpg {option} \"$@\"\n}}\n\n"));
+                script.push_str(Self::DEBSIG);
+            }
+            SignTool::Minisign => {
+                script.push_str(&format!(
+                    "sign() {{\n    {feed}minisign -S -s {} -m \"$1\" -x \"$1.sig\"\n}}\n\n",
+                    Self::quote(&self.key_file)
+                ));
+            }
+        }
+
+        for path in list {
+            let file = Self::quote(&path.display().to_string());
+
+            match self.tool {
+                SignTool::Gpg => {
+                    if path.extension().is_some_and(|x| x == "deb") {
+                        script.push_str(&format!("debsig {file}\n"));
+                    }
+
+                    script.push_str(&format!(
+                        "sign --armor --detach-sign --output {} {file}\n",
+                        Self::quote(&Self::signature(path, self.tool).display().to_string())
+                    ));
+                }
+                SignTool::Minisign => script.push_str(&format!("sign {file}\n")),
+            }
+        }
+
+        Ok(script)
+    }
+
+    /// embed an origin signature of the package's members into it, as an `_gpgorigin` member.
+    const DEBSIG: &'static str = "\
+# embed an origin signature into a Debian package, in the format debsigs writes.
+debsig() {
+    ar d \"$1\" _gpgorigin 2> /dev/null || true
+    ar p \"$1\" $(ar t \"$1\") > origin
+    sign --detach-sign --output _gpgorigin origin
+    ar q \"$1\" _gpgorigin
+    rm origin _gpgorigin
+}
+
+";
+
+    /// get the path of a file's detached signature.
+    pub fn signature(path: &Path, tool: SignTool) -> PathBuf {
+        let mut path = path.to_path_buf().into_os_string();
+        path.push(".");
+        path.push(tool.extension());
+
+        path.into()
+    }
+
+    /// check that a name can be used as a shell variable.
+    fn variable_name(name: &str) -> bool {
+        !name.starts_with(|x: char| x.is_ascii_digit())
+            && name.chars().all(|x| x.is_ascii_alphanumeric() || x == '_')
+    }
+
+    /// quote a shell argument.
+    fn quote(text: &str) -> String {
+        format!("'{}'", text.replace('\'', "'\\''"))
+    }
+}
+
+//================================================================
+
+#[derive(Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Sign {
+    /// Exporter name, to tell apart exporters of the same kind.
+    pub name: String,
+    /// Names of steps that must succeed before this one is run.
+    #[serde(default)]
+    pub need: Vec<String>,
+    /// Seconds the step may run before it is stopped. 0 for no limit.
+    #[serde(default)]
+    pub timeout: u64,
+    /// Whether the exporter is run.
+    pub enable: bool,
+    #[serde(skip)]
+    pub remove: bool,
+    #[serde(skip)]
+    status: ExportStatus,
+    #[serde(skip)]
+    handler: EventHandler,
+}
+
+impl Sign {
+    /// get every file to sign: every artifact produced so far, but for the compiled binaries.
+    fn source(meta: &Meta) -> Vec<&Artifact> {
+        meta.artifact
+            .iter()
+            .filter(|x| x.kind != ArtifactKind::Binary)
+            .collect()
+    }
+}
+
+#[typetag::serde]
+impl Export for Sign {
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    fn get_enable(&self) -> bool {
+        self.enable
+    }
+
+    fn get_remove(&self) -> bool {
+        self.remove
+    }
+
+    fn get_status(&mut self) -> &mut ExportStatus {
+        &mut self.status
+    }
+
+    fn get_handler(&mut self) -> &mut EventHandler {
+        &mut self.handler
+    }
+
+    fn get_path(&mut self) -> Vec<&mut String> {
+        Vec::default()
+    }
+
+    fn get_need(&self) -> &[String] {
+        &self.need
+    }
+
+    fn get_timeout(&self) -> u64 {
+        self.timeout
+    }
+
+    // after every package is built.
+    fn get_rank(&self) -> u32 {
+        1
+    }
+
+    fn get_artifact(&self, meta: &Meta) -> anyhow::Result<Vec<Artifact>> {
+        Ok(Self::source(meta)
+            .iter()
+            .map(|x| {
+                Artifact::new(
+                    &self.name,
+                    Signing::signature(&x.path, meta.signing.tool),
+                    ArtifactKind::Other,
+                    &x.architecture,
+                )
+            })
+            .collect())
+    }
+
+    fn validate(&self, meta: &Meta) -> Vec<String> {
+        meta.signing.validate(meta)
+    }
+
+    fn plan(&self, meta: &Meta) -> anyhow::Result<Plan> {
+        let source: Vec<PathBuf> = Self::source(meta).iter().map(|x| x.path.clone()).collect();
+        let work = meta.stage.join("sign");
+
+        if source.is_empty() {
+            let mut plan = Plan::default();
+            plan.warning
+                .push("No package was produced, so there is nothing to sign.".to_string());
+            return Ok(plan);
+        }
+
+        let mut command = std::process::Command::new(work.join("sign.sh"));
+        command.current_dir(&work);
+
+        let mut plan = Plan::new(Some(work), Some(command));
+
+        plan.write("sign.sh", meta.signing.script(&source)?, 0o755);
+
+        if meta.signing.tool == SignTool::Minisign
+            && source
+                .iter()
+                .any(|x| x.extension().is_some_and(|x| x == "deb"))
+        {
+            plan.warning.push(
+                "Debian packages only get a detached signature, debsigs signatures need GPG."
+                    .to_string(),
+            );
+        }
+
+        Ok(plan)
+    }
+
+    fn run(&mut self, meta: Meta) -> anyhow::Result<()> {
+        self.status = ExportStatus::InProgress;
+
+        let plan = self.plan(&meta)?;
+        let artifact = self.get_artifact(&meta)?;
+
+        self.execute_plan(plan, artifact);
+
+        Ok(())
+    }
+}
+
+//================================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::{os::unix::fs::PermissionsExt, process::Command, time::Duration};
+
+    /// run gpg on a throwaway keyring.
+    fn gpg(home: &Path) -> Command {
+        let mut command = Command::new("gpg");
+        command.arg("--homedir").arg(home).arg("--batch");
+        command
+    }
+
+    #[test]
+    #[ignore = "needs gpg and ar, run with --ignored"]
+    fn sign() {
+        let path = std::env::temp_dir().join(format!("boondle_sign_{}", std::process::id()));
+        let home = path.join("home");
+        std::fs::create_dir_all(&home).unwrap();
+        std::fs::set_permissions(&home, std::fs::Permissions::from_mode(0o700)).unwrap();
+
+        // a throwaway key, exported to a key file, with a passphrase.
+        let secret = ["--pinentry-mode", "loopback", "--passphrase", "secret"];
+        let status = gpg(&home)
+            .args(secret)
+            .args(["--quick-gen-key", "Boondle <test@boondle>"])
+            .args(["default", "default", "never"])
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let key = gpg(&home)
+            .args(secret)
+            .args(["--armor", "--export-secret-keys"])
+            .output()
+            .unwrap();
+        std::fs::write(path.join("key.asc"), key.stdout).unwrap();
+
+        // a stand-in Debian package.
+        let member = ["debian-binary", "control.tar.gz", "data.tar.gz"];
+
+        for member in member {
+            std::fs::write(path.join(member), member).unwrap();
+        }
+
+        let status = Command::new("ar")
+            .arg("q")
+            .arg("app.deb")
+            .args(member)
+            .current_dir(&path)
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let deb = path.join("app.deb");
+        let mut meta = Meta {
+            path: path.clone(),
+            stage: path.join("stage"),
+            ..Default::default()
+        };
+        meta.artifact.push(Artifact::new(
+            "deb",
+            deb.clone(),
+            ArtifactKind::Package,
+            "amd64",
+        ));
+        meta.signing = Signing {
+            key_file: path.join("key.asc").display().to_string(),
+            passphrase: "BOONDLE_TEST_PASSPHRASE".to_string(),
+            ..Default::default()
+        };
+
+        let mut step = Sign {
+            enable: true,
+            ..Default::default()
+        };
+
+        // the passphrase only reaches the script, through its own environment.
+        let mut plan = step.plan(&meta).unwrap();
+        plan.command
+            .as_mut()
+            .unwrap()
+            .env("BOONDLE_TEST_PASSPHRASE", "secret");
+
+        step.status = ExportStatus::InProgress;
+        step.execute_plan(plan, step.get_artifact(&meta).unwrap());
+
+        while !step.success_or_failure() {
+            step.poll_completion();
+            std::thread::sleep(Duration::from_millis(20));
+        }
+
+        assert!(
+            *step.get_status() == ExportStatus::Success,
+            "{}",
+            step.get_status()
+        );
+
+        // both the detached signature and the embedded one check out against the key.
+        let status = gpg(&home)
+            .arg("--verify")
+            .arg(path.join("app.deb.asc"))
+            .arg(&deb)
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        for (file, member) in [("origin", &member[..]), ("_gpgorigin", &["_gpgorigin"])] {
+            let data = Command::new("ar")
+                .arg("p")
+                .arg(&deb)
+                .args(member)
+                .output()
+                .unwrap();
+            std::fs::write(path.join(file), data.stdout).unwrap();
+        }
+
+        let status = gpg(&home)
+            .arg("--verify")
+            .arg(path.join("_gpgorigin"))
+            .arg(path.join("origin"))
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        // the keyring the key file was imported into is not left in the staging folder.
+        let mut list = vec![path.join("stage")];
+
+        while let Some(folder) = list.pop() {
+            for entry in std::fs::read_dir(folder).unwrap().flatten() {
+                assert_ne!(entry.file_name(), "private-keys-v1.d");
+
+                if entry.path().is_dir() {
+                    list.push(entry.path());
+                }
+            }
+        }
+
+        let _ = Command::new("gpgconf")
+            .arg("--homedir")
+            .arg(&home)
+            .args(["--kill", "gpg-agent"])
+            .status();
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+
+    #[test]
+    fn minisign() {
+        let meta = Meta {
+            path: PathBuf::from("/project"),
+            stage: PathBuf::from("/stage"),
+            signing: Signing {
+                tool: SignTool::Minisign,
+                key_file: "/project/key's.key".to_string(),
+                passphrase: "RELEASE_PASSPHRASE".to_string(),
+                ..Default::default()
+            },
+            artifact: vec![
+                Artifact::new(
+                    "deb",
+                    PathBuf::from("/project/app.deb"),
+                    ArtifactKind::Package,
+                    "amd64",
+                ),
+                Artifact::new(
+                    "app",
+                    PathBuf::from("/project/target/app"),
+                    ArtifactKind::Binary,
+                    "amd64",
+                ),
+            ],
+            ..Default::default()
+        };
+
+        let step = Sign::default();
+
+        // every package gets a .sig next to it, but the binary isn't signed.
+        let artifact = step.get_artifact(&meta).unwrap();
+        assert_eq!(artifact.len(), 1);
+        assert_eq!(artifact[0].path, PathBuf::from("/project/app.deb.sig"));
+        assert_eq!(artifact[0].architecture, "amd64");
+
+        let script = meta
+            .signing
+            .script(&[PathBuf::from("/project/app.deb")])
+            .unwrap();
+        assert!(script.contains(
+            "sign() {\n    printf '%s\\n' \"${RELEASE_PASSPHRASE}\" | \
+             minisign -S -s '/project/key'\\''s.key' -m \"$1\" -x \"$1.sig\"\n}"
+        ));
+        assert!(script.contains("sign '/project/app.deb'\n"));
+        assert!(!script.contains("debsig"));
+
+        // a Debian package signed with minisign gets no debsigs signature, and is warned about.
+        let plan = step.plan(&meta).unwrap();
+        assert_eq!(plan.warning.len(), 1);
+    }
+}
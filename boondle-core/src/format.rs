@@ -0,0 +1,180 @@
+/*
+* Copyright (c) 2025 luxreduxdelux
+*
+* Redistribution and use in source and binary forms, with or without
+* modification, are permitted provided that the following conditions are met:
+*
+* 1. Redistributions of source code must retain the above copyright notice,
+* this list of conditions and the following disclaimer.
+*
+* 2. Redistributions in binary form must reproduce the above copyright notice,
+* this list of conditions and the following disclaimer in the documentation
+* and/or other materials provided with the distribution.
+*
+* Subject to the terms and conditions of this license, each copyright holder
+* and contributor hereby grants to those receiving rights under this license
+* a perpetual, worldwide, non-exclusive, no-charge, royalty-free, irrevocable
+* (except for failure to satisfy the conditions of this license) patent license
+* to make, have made, use, offer to sell, sell, import, and otherwise transfer
+* this software, where such license applies only to those patent claims, already
+* acquired or hereafter acquired, licensable by such copyright holder or
+* contributor that are necessarily infringed by:
+*
+* (a) their Contribution(s) (the licensed copyrights of copyright holders and
+* non-copyrightable additions of contributors, in source or binary form) alone;
+* or
+*
+* (b) combination of their Contribution(s) with the work of authorship to which
+* such Contribution(s) was added by such copyright holder or contributor, if,
+* at the time the Contribution is added, such addition causes such combination
+* to be necessarily infringed. The patent license shall not apply to any other
+* combinations which include the Contribution.
+*
+* Except as expressly stated above, no rights or licenses from any copyright
+* holder or contributor is granted under this license, whether expressly, by
+* implication, estoppel or otherwise.
+*
+* DISCLAIMER
+*
+* THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+* AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+* IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+* DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS OR CONTRIBUTORS BE LIABLE
+* FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+* DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+* SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+* CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+* OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+* OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+*/
+
+use serde::Serialize;
+use serde_json::Value;
+use std::path::Path;
+
+//================================================================
+
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum Format {
+    #[default]
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl Format {
+    /// every project file name, in order of look-up priority.
+    pub const LIST_FILE: [(&'static str, Format); 5] = [
+        ("project.json", Format::Json),
+        ("boondle.toml", Format::Toml),
+        ("boondle.yaml", Format::Yaml),
+        ("boondle.yml", Format::Yaml),
+        ("project.yml", Format::Yaml),
+    ];
+
+    /// get the format of a project file from its extension.
+    pub fn from_path(path: &Path) -> anyhow::Result<Self> {
+        match path.extension().and_then(|x| x.to_str()) {
+            Some("json") => Ok(Format::Json),
+            Some("toml") => Ok(Format::Toml),
+            Some("yaml" | "yml") => Ok(Format::Yaml),
+            _ => Err(anyhow::Error::msg(format!(
+                "Format: Unknown project file extension for \"{}\", expected .json, .toml or .yaml.",
+                path.display()
+            ))),
+        }
+    }
+
+    /// get the default project file name of this format.
+    pub fn file_name(&self) -> &'static str {
+        Self::LIST_FILE
+            .iter()
+            .find(|(_, format)| format == self)
+            .map(|(name, _)| *name)
+            .unwrap_or_default()
+    }
+
+    /// parse a project file into a generic value, for migration.
+    pub fn read(&self, text: &str) -> anyhow::Result<Value> {
+        Ok(match self {
+            Format::Json => serde_json::from_str(text)?,
+            Format::Toml => toml::from_str(text)?,
+            Format::Yaml => serde_yaml_ng::from_str(text)?,
+        })
+    }
+
+    /// write a project file.
+    pub fn write<T: Serialize>(&self, data: &T) -> anyhow::Result<String> {
+        Ok(match self {
+            Format::Json => serde_json::to_string_pretty(data)?,
+            Format::Toml => toml::to_string_pretty(data)?,
+            Format::Yaml => serde_yaml_ng::to_string(data)?,
+        })
+    }
+}
+
+//================================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::project::Project;
+
+    const FIXTURE: &str = include_str!("../tests/fixture/project_v1.json");
+
+    #[test]
+    fn format_round_trip() {
+        let mut value: Value = serde_json::from_str(FIXTURE).unwrap();
+        value["compile"][0]["layout"] = serde_json::json!([
+            { "kind": "String",  "name": "TARGET", "data": "x86_64" },
+            { "kind": "Boolean", "name": "STRIP",  "data": true },
+        ]);
+
+        let project: Project = serde_json::from_value(value).unwrap();
+        let value = serde_json::to_value(&project).unwrap();
+
+        for format in [Format::Json, Format::Toml, Format::Yaml] {
+            let text = format.write(&project).unwrap();
+            let read = format.read(&text).unwrap();
+
+            assert_eq!(read, value, "{format:?}");
+            assert!(
+                serde_json::from_value::<Project>(read).is_ok(),
+                "{format:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn format_from_path() {
+        assert_eq!(
+            Format::from_path(Path::new("project.json")).unwrap(),
+            Format::Json
+        );
+        assert_eq!(
+            Format::from_path(Path::new("boondle.toml")).unwrap(),
+            Format::Toml
+        );
+        assert_eq!(
+            Format::from_path(Path::new("boondle.yml")).unwrap(),
+            Format::Yaml
+        );
+        assert!(Format::from_path(Path::new("project.ini")).is_err());
+
+        // every project file name is found in a project folder, and read in its own format.
+        let path = std::env::temp_dir().join(format!("boondle_format_{}", std::process::id()));
+
+        for (name, format) in Format::LIST_FILE {
+            std::fs::create_dir_all(&path).unwrap();
+            std::fs::write(path.join(name), "").unwrap();
+
+            let (folder, file) = Project::locate(path.clone()).unwrap();
+            assert_eq!(folder, std::fs::canonicalize(&path).unwrap());
+            assert_eq!(file, folder.join(name));
+            assert_eq!(Format::from_path(&file).unwrap(), format);
+
+            std::fs::remove_dir_all(&path).unwrap();
+        }
+    }
+}
@@ -0,0 +1,331 @@
+/*
+* Copyright (c) 2025 luxreduxdelux
+*
+* Redistribution and use in source and binary forms, with or without
+* modification, are permitted provided that the following conditions are met:
+*
+* 1. Redistributions of source code must retain the above copyright notice,
+* this list of conditions and the following disclaimer.
+*
+* 2. Redistributions in binary form must reproduce the above copyright notice,
+* this list of conditions and the following disclaimer in the documentation
+* and/or other materials provided with the distribution.
+*
+* Subject to the terms and conditions of this license, each copyright holder
+* and contributor hereby grants to those receiving rights under this license
+* a perpetual, worldwide, non-exclusive, no-charge, royalty-free, irrevocable
+* (except for failure to satisfy the conditions of this license) patent license
+* to make, have made, use, offer to sell, sell, import, and otherwise transfer
+* this software, where such license applies only to those patent claims, already
+* acquired or hereafter acquired, licensable by such copyright holder or
+* contributor that are necessarily infringed by:
+*
+* (a) their Contribution(s) (the licensed copyrights of copyright holders and
+* non-copyrightable additions of contributors, in source or binary form) alone;
+* or
+*
+* (b) combination of their Contribution(s) with the work of authorship to which
+* such Contribution(s) was added by such copyright holder or contributor, if,
+* at the time the Contribution is added, such addition causes such combination
+* to be necessarily infringed. The patent license shall not apply to any other
+* combinations which include the Contribution.
+*
+* Except as expressly stated above, no rights or licenses from any copyright
+* holder or contributor is granted under this license, whether expressly, by
+* implication, estoppel or otherwise.
+*
+* DISCLAIMER
+*
+* THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+* AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+* IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+* DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS OR CONTRIBUTORS BE LIABLE
+* FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+* DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+* SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+* CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+* OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+* OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+*/
+
+use crate::{
+    exporter::{
+        artifact::Artifact,
+        export::{Export, ExportStatus, kind},
+    },
+    scheduler::Scheduler,
+};
+
+//================================================================
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+//================================================================
+
+/// a step of a past run.
+#[derive(Serialize, Deserialize)]
+pub struct RunStep {
+    pub name: String,
+    pub kind: String,
+    pub status: String,
+    pub success: bool,
+    pub code: Option<i32>,
+    /// seconds the step ran for.
+    pub duration: Option<f64>,
+    /// log file, relative to the run folder. empty if the step did not run.
+    pub log: String,
+    /// log text, once loaded for display.
+    #[serde(skip)]
+    pub text: Option<String>,
+}
+
+/// a past compile or package run, as written to its folder in the project's history.
+#[derive(Serialize, Deserialize)]
+pub struct Run {
+    #[serde(skip)]
+    pub path: PathBuf,
+    pub stage: String,
+    pub profile: String,
+    pub date: String,
+    /// seconds the run took.
+    pub duration: f64,
+    pub success: bool,
+    /// project metadata, as resolved for the run.
+    pub meta: Value,
+    pub artifact: Vec<Artifact>,
+    pub step: Vec<RunStep>,
+}
+
+impl Run {
+    pub const FOLDER: &'static str = "boondle_history";
+    const FILE: &'static str = "run.json";
+    /// prefix of the lines of a log file that were written to stderr.
+    pub const STDERR: &'static str = "[stderr] ";
+
+    /// write a finished run to a new timestamped folder in the project's history.
+    pub fn write(
+        stage: &str,
+        profile: &str,
+        schedule: &Scheduler,
+        list: &mut [Box<dyn Export>],
+    ) -> anyhow::Result<PathBuf> {
+        let meta = &schedule.meta;
+        let date = meta.template.format("{date}")?;
+        let time = meta.template.format("{time}")?;
+
+        // runs started in the same second get a counter.
+        let name = format!("{date}_{time}_{stage}");
+        let mut path = meta.path.join(Self::FOLDER).join(&name);
+        let mut count = 1;
+
+        while path.exists() {
+            count += 1;
+            path = meta.path.join(Self::FOLDER).join(format!("{name}_{count}"));
+        }
+
+        std::fs::create_dir_all(&path)?;
+
+        let mut step = Vec::new();
+
+        for (i, export) in list.iter_mut().enumerate() {
+            let kind = kind(export.as_ref());
+            let name = export.get_name().to_string();
+            let mut log = String::default();
+
+            if !export.get_log().is_empty() {
+                let label = if name.is_empty() { &kind } else { &name };
+                let label: String = label
+                    .chars()
+                    .map(|x| if x.is_alphanumeric() { x } else { '_' })
+                    .collect();
+
+                log = format!("{i:02}_{label}.log");
+
+                let text: Vec<String> = export
+                    .get_log()
+                    .iter()
+                    .map(|line| {
+                        if line.stderr {
+                            format!("{}{}", Self::STDERR, line.text)
+                        } else {
+                            line.text.clone()
+                        }
+                    })
+                    .collect();
+
+                std::fs::write(path.join(&log), text.join("\n") + "\n")?;
+            }
+
+            let status = export.get_status();
+
+            step.push(RunStep {
+                name,
+                kind,
+                success: matches!(status, ExportStatus::Success | ExportStatus::Skipped),
+                status: status.to_string(),
+                code: export.get_code(),
+                duration: export.get_duration().map(|x| x.as_secs_f64()),
+                log,
+                text: None,
+            });
+        }
+
+        let run = Self {
+            path: path.clone(),
+            stage: stage.to_string(),
+            profile: profile.to_string(),
+            date: format!("{date} {}:{}:{}", &time[0..2], &time[2..4], &time[4..6]),
+            duration: schedule.start.elapsed().as_secs_f64(),
+            success: step.iter().all(|x| x.success),
+            meta: serde_json::to_value(meta)?,
+            artifact: schedule.artifact.clone(),
+            step,
+        };
+
+        std::fs::write(path.join(Self::FILE), serde_json::to_string_pretty(&run)?)?;
+
+        Ok(path)
+    }
+
+    /// get every run in the history of the project at a path, the latest first, along with a
+    /// warning for every run that could not be read. a run that can't be read is left out.
+    pub fn list(path: &Path) -> anyhow::Result<(Vec<Self>, Vec<String>)> {
+        let folder = path.join(Self::FOLDER);
+        let mut result = Vec::new();
+        let mut warning = Vec::new();
+
+        if !folder.is_dir() {
+            return Ok((result, warning));
+        }
+
+        for entry in std::fs::read_dir(folder)?.flatten() {
+            let path = entry.path();
+            let file = path.join(Self::FILE);
+
+            if !file.is_file() {
+                continue;
+            }
+
+            let run = std::fs::read_to_string(&file)
+                .map_err(anyhow::Error::from)
+                .and_then(|data| Ok(serde_json::from_str::<Self>(&data)?));
+
+            match run {
+                Ok(mut run) => {
+                    run.path = path;
+                    result.push(run);
+                }
+                Err(error) => warning.push(format!(
+                    "History: Could not read \"{}\", it was skipped: {error}.",
+                    file.display()
+                )),
+            }
+        }
+
+        result.sort_by(|a, b| b.name().cmp(a.name()));
+
+        Ok((result, warning))
+    }
+
+    /// find a run in the history of the project at a path, by folder name or by number, 0 being the latest.
+    pub fn find(path: &Path, run: &str) -> anyhow::Result<Self> {
+        let (list, _) = Self::list(path)?;
+        let index = run.parse::<usize>().ok();
+
+        list.into_iter()
+            .enumerate()
+            .find(|(i, x)| Some(*i) == index || x.name() == run)
+            .map(|(_, x)| x)
+            .ok_or(anyhow::Error::msg(format!("History: No run \"{run}\".")))
+    }
+
+    /// get the name of the run's folder.
+    pub fn name(&self) -> &str {
+        self.path
+            .file_name()
+            .and_then(|x| x.to_str())
+            .unwrap_or_default()
+    }
+
+    /// get the full path to the log file of a step, if the step ran.
+    pub fn log_path(&self, step: &RunStep) -> Option<PathBuf> {
+        if step.log.is_empty() {
+            None
+        } else {
+            Some(self.path.join(&step.log))
+        }
+    }
+}
+
+//================================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{project::Meta, template::Template};
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn write_list() {
+        let path = std::env::temp_dir().join(format!("boondle_history_{}", std::process::id()));
+        std::fs::create_dir_all(&path).unwrap();
+
+        let script = path.join("step.sh");
+        std::fs::write(&script, "#!/bin/sh\necho hello\necho oops >&2\n").unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut list: Vec<Box<dyn Export>> = vec![
+            serde_json::from_value(serde_json::json!({
+                "type": "Script",
+                "name": "step",
+                "script": script,
+                "layout": [],
+                "enable": true,
+            }))
+            .unwrap(),
+        ];
+
+        let meta = Meta {
+            path: path.clone(),
+            template: Template::new(&path),
+            ..Default::default()
+        };
+
+        let mut schedule = Scheduler::new(&mut list, meta, 1, None).unwrap();
+
+        while !schedule.poll(&mut list) {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        let folder = Run::write("package", "nightly", &schedule, &mut list).unwrap();
+
+        // a run that can't be read is skipped with a warning, rather than failing the whole list.
+        let broken = path.join(Run::FOLDER).join("0000-00-00_000000_package");
+        std::fs::create_dir_all(&broken).unwrap();
+        std::fs::write(broken.join(Run::FILE), "{").unwrap();
+
+        let (run, warning) = Run::list(&path).unwrap();
+        assert_eq!(run.len(), 1);
+        assert_eq!(warning.len(), 1);
+        assert!(warning[0].contains("0000-00-00_000000_package"));
+
+        let run = &run[0];
+        assert_eq!(run.path, folder);
+        assert_eq!(
+            (run.stage.as_str(), run.profile.as_str()),
+            ("package", "nightly")
+        );
+        assert!(run.success);
+        assert_eq!(run.step[0].status, "Success");
+
+        let log = std::fs::read_to_string(run.log_path(&run.step[0]).unwrap()).unwrap();
+        assert_eq!(log, format!("hello\n{}oops\n", Run::STDERR));
+
+        assert_eq!(Run::find(&path, "0").unwrap().name(), run.name());
+        assert!(Run::find(&path, "1").is_err());
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+}
@@ -0,0 +1,299 @@
+/*
+* Copyright (c) 2025 luxreduxdelux
+*
+* Redistribution and use in source and binary forms, with or without
+* modification, are permitted provided that the following conditions are met:
+*
+* 1. Redistributions of source code must retain the above copyright notice,
+* this list of conditions and the following disclaimer.
+*
+* 2. Redistributions in binary form must reproduce the above copyright notice,
+* this list of conditions and the following disclaimer in the documentation
+* and/or other materials provided with the distribution.
+*
+* Subject to the terms and conditions of this license, each copyright holder
+* and contributor hereby grants to those receiving rights under this license
+* a perpetual, worldwide, non-exclusive, no-charge, royalty-free, irrevocable
+* (except for failure to satisfy the conditions of this license) patent license
+* to make, have made, use, offer to sell, sell, import, and otherwise transfer
+* this software, where such license applies only to those patent claims, already
+* acquired or hereafter acquired, licensable by such copyright holder or
+* contributor that are necessarily infringed by:
+*
+* (a) their Contribution(s) (the licensed copyrights of copyright holders and
+* non-copyrightable additions of contributors, in source or binary form) alone;
+* or
+*
+* (b) combination of their Contribution(s) with the work of authorship to which
+* such Contribution(s) was added by such copyright holder or contributor, if,
+* at the time the Contribution is added, such addition causes such combination
+* to be necessarily infringed. The patent license shall not apply to any other
+* combinations which include the Contribution.
+*
+* Except as expressly stated above, no rights or licenses from any copyright
+* holder or contributor is granted under this license, whether expressly, by
+* implication, estoppel or otherwise.
+*
+* DISCLAIMER
+*
+* THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+* AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+* IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+* DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS OR CONTRIBUTORS BE LIABLE
+* FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+* DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+* SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+* CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+* OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+* OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+*/
+
+use crate::inspect::{Entry, overflow, read, squashfs};
+
+//================================================================
+
+use serde::Serialize;
+use std::fmt::Display;
+
+//================================================================
+
+/// what an AppImage holds.
+#[derive(Debug, Serialize)]
+pub struct AppImageReport {
+    /// AppImage type of the runtime: 1 for an ISO 9660 image, 2 for a squashfs image.
+    pub runtime: u8,
+    /// architecture the runtime is built for.
+    pub architecture: String,
+    /// update information, e.g. "gh-releases-zsync|owner|repo|latest|*.zsync".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update: Option<String>,
+    /// embedded signature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// embedded public key of the signature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// byte offset of the filesystem image, right after the runtime.
+    pub offset: u64,
+    /// compression of the filesystem image.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compression: Option<String>,
+    /// every file in the filesystem image. empty for a type 1 image.
+    pub file: Vec<Entry>,
+}
+
+impl Display for AppImageReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Format: AppImage (type {})", self.runtime)?;
+        writeln!(f, "Architecture: {}", self.architecture)?;
+        writeln!(f, "Update: {}", self.update.as_deref().unwrap_or("None"))?;
+        writeln!(
+            f,
+            "Signature: {}",
+            if self.signature.is_some() {
+                "Embedded"
+            } else {
+                "None"
+            }
+        )?;
+        writeln!(f, "Offset: {}", self.offset)?;
+
+        if let Some(compression) = &self.compression {
+            writeln!(f, "Compression: {compression}")?;
+        }
+
+        for (label, text) in [("Signature", &self.signature), ("Key", &self.key)] {
+            if let Some(text) = text {
+                writeln!(f, "\n{label}:")?;
+
+                for line in text.lines() {
+                    writeln!(f, "    | {line}")?;
+                }
+            }
+        }
+
+        if self.runtime == 2 {
+            writeln!(f, "\nFiles:")?;
+
+            for entry in &self.file {
+                writeln!(f, "  {entry}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// section of an ELF file.
+struct Section {
+    name: usize,
+    offset: usize,
+    size: usize,
+}
+
+/// read an AppImage: its runtime, the sections it embeds and, for a type 2 image, every file in
+/// its filesystem image.
+pub fn inspect(data: &[u8]) -> anyhow::Result<AppImageReport> {
+    if data.get(5) != Some(&1) {
+        return Err(anyhow::Error::msg(
+            "Inspect: Only little-endian AppImages are supported.",
+        ));
+    }
+
+    let wide = data.get(4) == Some(&2);
+
+    // the filesystem image starts right after the section headers, at the end of the runtime.
+    let (offset, size, count, index) = if wide {
+        (
+            read(data, 0x28, 8)?,
+            read(data, 0x3A, 2)?,
+            read(data, 0x3C, 2)?,
+            read(data, 0x3E, 2)?,
+        )
+    } else {
+        (
+            read(data, 0x20, 4)?,
+            read(data, 0x2E, 2)?,
+            read(data, 0x30, 2)?,
+            read(data, 0x32, 2)?,
+        )
+    };
+
+    // every offset and size comes from the file itself, so a broken file must not overflow them.
+    let end = size
+        .checked_mul(count)
+        .and_then(|x| x.checked_add(offset))
+        .and_then(|x| usize::try_from(x).ok())
+        .ok_or_else(overflow)?;
+
+    let mut section = Vec::new();
+
+    for i in 0..count {
+        let at = usize::try_from(offset + i * size).map_err(|_| overflow())?;
+        let field = |add: usize, size: usize| -> anyhow::Result<usize> {
+            let value = read(data, at.checked_add(add).ok_or_else(overflow)?, size)?;
+
+            usize::try_from(value).map_err(|_| overflow())
+        };
+
+        section.push(if wide {
+            Section {
+                name: field(0, 4)?,
+                offset: field(0x18, 8)?,
+                size: field(0x20, 8)?,
+            }
+        } else {
+            Section {
+                name: field(0, 4)?,
+                offset: field(0x10, 4)?,
+                size: field(0x14, 4)?,
+            }
+        });
+    }
+
+    // get the text of a section by name, if it has any.
+    let text = |name: &str| -> Option<String> {
+        let table = section.get(index as usize)?;
+
+        section
+            .iter()
+            .find(|x| {
+                table
+                    .offset
+                    .checked_add(x.name)
+                    .and_then(|at| data.get(at..))
+                    .and_then(|x| x.split(|x| *x == 0).next())
+                    == Some(name.as_bytes())
+            })
+            .and_then(|x| data.get(x.offset..x.offset.checked_add(x.size)?))
+            .map(|x| {
+                String::from_utf8_lossy(x)
+                    .trim_matches('\0')
+                    .trim()
+                    .to_string()
+            })
+            .filter(|x| !x.is_empty())
+    };
+
+    let machine = read(data, 0x12, 2)?;
+    let architecture = match machine {
+        0x03 => "i386".to_string(),
+        0x28 => "armhf".to_string(),
+        0x3E => "x86_64".to_string(),
+        0xB7 => "aarch64".to_string(),
+        _ => format!("{machine:#x}"),
+    };
+
+    let runtime = data.get(10).copied().unwrap_or_default();
+
+    let (compression, file) = if runtime == 2 {
+        let image = data.get(end..).unwrap_or_default();
+        let (compression, file) = squashfs::list(image)?;
+
+        (Some(compression), file)
+    } else {
+        (None, Vec::default())
+    };
+
+    Ok(AppImageReport {
+        runtime,
+        architecture,
+        update: text(".upd_info"),
+        signature: text(".sha256_sig"),
+        key: text(".sig_key"),
+        offset: end as u64,
+        compression,
+        file,
+    })
+}
+
+//================================================================
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn inspect() {
+        // a 64-bit ELF header of an x86_64 type 1 AppImage, with no section.
+        let mut data = vec![0; 64];
+        data[..4].copy_from_slice(b"\x7fELF");
+        data[4] = 2;
+        data[5] = 1;
+        data[8..11].copy_from_slice(b"AI\x01");
+        data[0x12] = 0x3E;
+        data[0x28] = 64;
+
+        let report = super::inspect(&data).unwrap();
+        assert_eq!(report.runtime, 1);
+        assert_eq!(report.architecture, "x86_64");
+        assert_eq!(report.offset, 64);
+        assert!(report.update.is_none());
+
+        // section headers far past the end of the file are an error, not an overflow.
+        data[0x28..0x30].copy_from_slice(&u64::MAX.to_le_bytes());
+        data[0x3A] = 0xFF;
+        data[0x3C] = 0xFF;
+        assert!(super::inspect(&data).is_err());
+
+        data[0x28..0x30].copy_from_slice(&(u64::MAX / 2).to_le_bytes());
+        assert!(super::inspect(&data).is_err());
+    }
+
+    #[test]
+    fn display() {
+        let report = super::AppImageReport {
+            runtime: 1,
+            architecture: "x86_64".to_string(),
+            update: None,
+            signature: Some("-----BEGIN PGP SIGNATURE-----".to_string()),
+            key: Some("-----BEGIN PGP PUBLIC KEY BLOCK-----".to_string()),
+            offset: 64,
+            compression: None,
+            file: Vec::new(),
+        };
+
+        // both the signature and the key it was made with are shown.
+        let text = report.to_string();
+        assert!(text.contains("\nSignature:\n    | -----BEGIN PGP SIGNATURE-----\n"));
+        assert!(text.contains("\nKey:\n    | -----BEGIN PGP PUBLIC KEY BLOCK-----\n"));
+    }
+}
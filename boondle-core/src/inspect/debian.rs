@@ -0,0 +1,318 @@
+/*
+* Copyright (c) 2025 luxreduxdelux
+*
+* Redistribution and use in source and binary forms, with or without
+* modification, are permitted provided that the following conditions are met:
+*
+* 1. Redistributions of source code must retain the above copyright notice,
+* this list of conditions and the following disclaimer.
+*
+* 2. Redistributions in binary form must reproduce the above copyright notice,
+* this list of conditions and the following disclaimer in the documentation
+* and/or other materials provided with the distribution.
+*
+* Subject to the terms and conditions of this license, each copyright holder
+* and contributor hereby grants to those receiving rights under this license
+* a perpetual, worldwide, non-exclusive, no-charge, royalty-free, irrevocable
+* (except for failure to satisfy the conditions of this license) patent license
+* to make, have made, use, offer to sell, sell, import, and otherwise transfer
+* this software, where such license applies only to those patent claims, already
+* acquired or hereafter acquired, licensable by such copyright holder or
+* contributor that are necessarily infringed by:
+*
+* (a) their Contribution(s) (the licensed copyrights of copyright holders and
+* non-copyrightable additions of contributors, in source or binary form) alone;
+* or
+*
+* (b) combination of their Contribution(s) with the work of authorship to which
+* such Contribution(s) was added by such copyright holder or contributor, if,
+* at the time the Contribution is added, such addition causes such combination
+* to be necessarily infringed. The patent license shall not apply to any other
+* combinations which include the Contribution.
+*
+* Except as expressly stated above, no rights or licenses from any copyright
+* holder or contributor is granted under this license, whether expressly, by
+* implication, estoppel or otherwise.
+*
+* DISCLAIMER
+*
+* THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+* AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+* IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+* DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS OR CONTRIBUTORS BE LIABLE
+* FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+* DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+* SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+* CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+* OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+* OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+*/
+
+use crate::inspect::{Compression, Entry, EntryKind};
+
+//================================================================
+
+use serde::Serialize;
+use std::{fmt::Display, io::Read};
+
+//================================================================
+
+/// field of a Debian control file.
+#[derive(Debug, Serialize)]
+pub struct Field {
+    pub name: String,
+    pub value: String,
+}
+
+/// maintainer script of a Debian package.
+#[derive(Debug, Serialize)]
+pub struct Script {
+    pub name: String,
+    pub data: String,
+}
+
+/// what a Debian package holds.
+#[derive(Debug, Serialize)]
+pub struct DebianReport {
+    /// version of the package format, from "debian-binary".
+    pub version: String,
+    /// fields of the control file, in order.
+    pub control: Vec<Field>,
+    /// maintainer scripts, e.g. "postinst".
+    pub script: Vec<Script>,
+    /// every file the package installs.
+    pub file: Vec<Entry>,
+    /// embedded signatures, by member name, e.g. "_gpgorigin".
+    pub signature: Vec<String>,
+}
+
+impl DebianReport {
+    /// every maintainer script a package may hold.
+    const LIST_SCRIPT: [&'static str; 5] = ["preinst", "postinst", "prerm", "postrm", "config"];
+
+    /// parse a Debian control file into its fields, keeping continuation lines.
+    pub fn parse_control(text: &str) -> Vec<Field> {
+        let mut result: Vec<Field> = Vec::new();
+
+        for line in text.lines() {
+            if line.starts_with([' ', '\t']) {
+                if let Some(field) = result.last_mut() {
+                    field.value.push('\n');
+                    field.value.push_str(line.trim());
+                }
+            } else if let Some((name, value)) = line.split_once(':') {
+                result.push(Field {
+                    name: name.trim().to_string(),
+                    value: value.trim().to_string(),
+                });
+            }
+        }
+
+        result
+    }
+}
+
+impl Display for DebianReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Format: Debian package ({})", self.version)?;
+
+        if self.signature.is_empty() {
+            writeln!(f, "Signature: None")?;
+        } else {
+            writeln!(f, "Signature: {}", self.signature.join(", "))?;
+        }
+
+        writeln!(f, "\nControl:")?;
+
+        for field in &self.control {
+            writeln!(
+                f,
+                "  {}: {}",
+                field.name,
+                field.value.replace('\n', "\n    ")
+            )?;
+        }
+
+        if !self.script.is_empty() {
+            writeln!(f, "\nMaintainer Scripts:")?;
+
+            for script in &self.script {
+                writeln!(f, "  {}:", script.name)?;
+
+                for line in script.data.lines() {
+                    writeln!(f, "    | {line}")?;
+                }
+            }
+        }
+
+        writeln!(f, "\nFiles:")?;
+
+        for entry in &self.file {
+            writeln!(f, "  {entry}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// read a Debian package: its control file, maintainer scripts and every file it installs.
+pub fn inspect(data: &[u8]) -> anyhow::Result<DebianReport> {
+    let mut archive = ar::Archive::new(data);
+    let mut version = String::default();
+    let mut control = None;
+    let mut content = None;
+    let mut signature = Vec::new();
+
+    while let Some(entry) = archive.next_entry() {
+        let mut entry = entry?;
+        let name = String::from_utf8_lossy(entry.header().identifier()).to_string();
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data)?;
+
+        if name == "debian-binary" {
+            version = String::from_utf8_lossy(&data).trim().to_string();
+        } else if name.starts_with("control.tar") {
+            control = Some(untar(&name, data)?);
+        } else if name.starts_with("data.tar") {
+            content = Some(untar(&name, data)?);
+        } else if name.starts_with("_gpg") {
+            signature.push(name);
+        }
+    }
+
+    let (Some(control), Some(content)) = (control, content) else {
+        return Err(anyhow::Error::msg(
+            "Inspect: Archive has no control.tar or data.tar member, it is not a Debian package.",
+        ));
+    };
+
+    let mut report = DebianReport {
+        version,
+        control: Vec::default(),
+        script: Vec::default(),
+        file: list(&content)?,
+        signature,
+    };
+
+    for entry in tar::Archive::new(&control[..]).entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.display().to_string();
+        let name = path.trim_start_matches("./").to_string();
+        let mut data = String::new();
+
+        if name == "control" {
+            entry.read_to_string(&mut data)?;
+            report.control = DebianReport::parse_control(&data);
+        } else if DebianReport::LIST_SCRIPT.contains(&name.as_str()) {
+            entry.read_to_string(&mut data)?;
+            report.script.push(Script { name, data });
+        }
+    }
+
+    Ok(report)
+}
+
+/// decompress a tar member of a Debian package, by the extension of its name.
+fn untar(name: &str, data: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+    match name.rsplit_once('.').map(|(_, x)| x) {
+        Some("tar") => Ok(data),
+        Some("gz") => Compression::Gzip.decompress(&data),
+        Some("xz") => Compression::Xz.decompress(&data),
+        Some("zst") => Compression::Zstd.decompress(&data),
+        _ => Err(anyhow::Error::msg(format!(
+            "Inspect: Member \"{name}\" has an unsupported compression."
+        ))),
+    }
+}
+
+/// list every file in a tar archive.
+fn list(data: &[u8]) -> anyhow::Result<Vec<Entry>> {
+    let mut result = Vec::new();
+
+    for entry in tar::Archive::new(data).entries()? {
+        let entry = entry?;
+        let header = entry.header();
+        let kind = header.entry_type();
+
+        let kind = if kind.is_dir() {
+            EntryKind::Folder
+        } else if kind.is_symlink() || kind.is_hard_link() {
+            EntryKind::Link
+        } else if kind.is_file() {
+            EntryKind::File
+        } else {
+            EntryKind::Other
+        };
+
+        result.push(Entry::new(
+            entry.path()?.display().to_string(),
+            kind,
+            header.mode()?,
+            header.size()?,
+            entry.link_name()?.map(|x| x.display().to_string()),
+        ));
+    }
+
+    Ok(result)
+}
+
+//================================================================
+
+#[cfg(test)]
+mod test {
+    use flate2::write::GzEncoder;
+
+    /// build a tar.gz archive of every file given, with its mode.
+    fn tar(list: &[(&str, &str, u32)]) -> Vec<u8> {
+        let mut builder =
+            tar::Builder::new(GzEncoder::new(Vec::new(), flate2::Compression::default()));
+
+        for (path, data, mode) in list {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(*mode);
+            header.set_cksum();
+
+            builder
+                .append_data(&mut header, path, data.as_bytes())
+                .unwrap();
+        }
+
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    #[test]
+    fn inspect() {
+        let control = tar(&[
+            (
+                "./control",
+                "Package: app\nVersion: 1.0\nDescription: An app.\n Longer text.\n",
+                0o644,
+            ),
+            ("./postinst", "#!/bin/sh\necho hello\n", 0o755),
+        ]);
+        let content = tar(&[("./usr/bin/app", "binary", 0o755)]);
+
+        let mut builder = ar::Builder::new(Vec::new());
+
+        for (name, data) in [
+            ("debian-binary", b"2.0\n".to_vec()),
+            ("control.tar.gz", control),
+            ("data.tar.gz", content),
+        ] {
+            let header = ar::Header::new(name.as_bytes().to_vec(), data.len() as u64);
+            builder.append(&header, &data[..]).unwrap();
+        }
+
+        let report = super::inspect(&builder.into_inner().unwrap()).unwrap();
+
+        assert_eq!(report.version, "2.0");
+        assert_eq!(report.control.len(), 3);
+        assert_eq!(report.control[2].value, "An app.\nLonger text.");
+        assert_eq!(report.script[0].name, "postinst");
+        assert_eq!(report.file[0].path, "usr/bin/app");
+        assert_eq!(report.file[0].mode, "0755");
+        assert_eq!(report.file[0].size, 6);
+        assert!(report.signature.is_empty());
+    }
+}
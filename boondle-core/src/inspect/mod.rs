@@ -0,0 +1,224 @@
+/*
+* Copyright (c) 2025 luxreduxdelux
+*
+* Redistribution and use in source and binary forms, with or without
+* modification, are permitted provided that the following conditions are met:
+*
+* 1. Redistributions of source code must retain the above copyright notice,
+* this list of conditions and the following disclaimer.
+*
+* 2. Redistributions in binary form must reproduce the above copyright notice,
+* this list of conditions and the following disclaimer in the documentation
+* and/or other materials provided with the distribution.
+*
+* Subject to the terms and conditions of this license, each copyright holder
+* and contributor hereby grants to those receiving rights under this license
+* a perpetual, worldwide, non-exclusive, no-charge, royalty-free, irrevocable
+* (except for failure to satisfy the conditions of this license) patent license
+* to make, have made, use, offer to sell, sell, import, and otherwise transfer
+* this software, where such license applies only to those patent claims, already
+* acquired or hereafter acquired, licensable by such copyright holder or
+* contributor that are necessarily infringed by:
+*
+* (a) their Contribution(s) (the licensed copyrights of copyright holders and
+* non-copyrightable additions of contributors, in source or binary form) alone;
+* or
+*
+* (b) combination of their Contribution(s) with the work of authorship to which
+* such Contribution(s) was added by such copyright holder or contributor, if,
+* at the time the Contribution is added, such addition causes such combination
+* to be necessarily infringed. The patent license shall not apply to any other
+* combinations which include the Contribution.
+*
+* Except as expressly stated above, no rights or licenses from any copyright
+* holder or contributor is granted under this license, whether expressly, by
+* implication, estoppel or otherwise.
+*
+* DISCLAIMER
+*
+* THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+* AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+* IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+* DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS OR CONTRIBUTORS BE LIABLE
+* FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+* DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+* SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+* CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+* OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+* OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+*/
+
+pub mod app_image;
+pub mod debian;
+pub mod squashfs;
+
+//================================================================
+
+use crate::inspect::{app_image::AppImageReport, debian::DebianReport};
+
+//================================================================
+
+use serde::Serialize;
+use std::{fmt::Display, io::Read, path::Path};
+
+//================================================================
+
+/// what a package holds, read from the package itself.
+#[derive(Serialize)]
+#[serde(tag = "format")]
+pub enum Report {
+    Debian(DebianReport),
+    AppImage(AppImageReport),
+}
+
+impl Report {
+    /// get the report as pretty-printed JSON.
+    pub fn json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Report::Debian(report) => report.fmt(f),
+            Report::AppImage(report) => report.fmt(f),
+        }
+    }
+}
+
+/// kind of a file in a package.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum EntryKind {
+    File,
+    Folder,
+    Link,
+    Other,
+}
+
+/// file in a package.
+#[derive(Debug, Serialize)]
+pub struct Entry {
+    pub path: String,
+    pub kind: EntryKind,
+    /// permission bits, in octal.
+    pub mode: String,
+    pub size: u64,
+    /// target of a link.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+}
+
+impl Entry {
+    pub fn new(path: String, kind: EntryKind, mode: u32, size: u64, link: Option<String>) -> Self {
+        Self {
+            path,
+            kind,
+            mode: format!("{:04o}", mode & 0o7777),
+            size,
+            link,
+        }
+    }
+}
+
+impl Display for Entry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mode = u32::from_str_radix(&self.mode, 8).unwrap_or_default();
+        let mut text = String::from(match self.kind {
+            EntryKind::File => "-",
+            EntryKind::Folder => "d",
+            EntryKind::Link => "l",
+            EntryKind::Other => "?",
+        });
+
+        for shift in [6, 3, 0] {
+            let bit = mode >> shift;
+            text.push(if bit & 4 != 0 { 'r' } else { '-' });
+            text.push(if bit & 2 != 0 { 'w' } else { '-' });
+            text.push(if bit & 1 != 0 { 'x' } else { '-' });
+        }
+
+        write!(f, "{text} {:>10} {}", self.size, self.path)?;
+
+        if let Some(link) = &self.link {
+            write!(f, " -> {link}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// compression of a package member or of a filesystem block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compression {
+    Gzip,
+    Zlib,
+    Xz,
+    Zstd,
+}
+
+impl Compression {
+    /// decompress a whole stream.
+    pub fn decompress(&self, data: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let mut result = Vec::new();
+
+        match self {
+            Self::Gzip => {
+                flate2::read::GzDecoder::new(data).read_to_end(&mut result)?;
+            }
+            Self::Zlib => {
+                flate2::read::ZlibDecoder::new(data).read_to_end(&mut result)?;
+            }
+            Self::Xz => lzma_rs::xz_decompress(&mut &data[..], &mut result)
+                .map_err(|error| anyhow::Error::msg(format!("Inspect: {error}.")))?,
+            Self::Zstd => {
+                ruzstd::decoding::StreamingDecoder::new(data)
+                    .map_err(|error| anyhow::Error::msg(format!("Inspect: {error}.")))?
+                    .read_to_end(&mut result)?;
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// error for an offset or a size read from a file that is too large to be valid.
+pub(crate) fn overflow() -> anyhow::Error {
+    anyhow::Error::msg("Inspect: File points past the end of itself, it may be corrupt.")
+}
+
+/// read a little-endian integer of 1 to 8 bytes.
+pub(crate) fn read(data: &[u8], at: usize, size: usize) -> anyhow::Result<u64> {
+    let byte = at
+        .checked_add(size)
+        .and_then(|end| data.get(at..end))
+        .ok_or_else(|| {
+            anyhow::Error::msg("Inspect: File ends before it should, it may be cut short.")
+        })?;
+
+    Ok(byte
+        .iter()
+        .rev()
+        .fold(0, |value, byte| (value << 8) | u64::from(*byte)))
+}
+
+/// read what a Debian package or an AppImage holds, telling them apart by their content.
+pub fn inspect(path: &Path) -> anyhow::Result<Report> {
+    let data = std::fs::read(path).map_err(|error| {
+        anyhow::Error::msg(format!(
+            "Inspect: Could not read \"{}\": {error}.",
+            path.display()
+        ))
+    })?;
+
+    if data.starts_with(b"!<arch>\n") {
+        Ok(Report::Debian(debian::inspect(&data)?))
+    } else if data.starts_with(b"\x7fELF") && data.get(8..10) == Some(b"AI") {
+        Ok(Report::AppImage(app_image::inspect(&data)?))
+    } else {
+        Err(anyhow::Error::msg(format!(
+            "Inspect: \"{}\" is neither a Debian package nor an AppImage.",
+            path.display()
+        )))
+    }
+}
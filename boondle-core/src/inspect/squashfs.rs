@@ -0,0 +1,396 @@
+/*
+* Copyright (c) 2025 luxreduxdelux
+*
+* Redistribution and use in source and binary forms, with or without
+* modification, are permitted provided that the following conditions are met:
+*
+* 1. Redistributions of source code must retain the above copyright notice,
+* this list of conditions and the following disclaimer.
+*
+* 2. Redistributions in binary form must reproduce the above copyright notice,
+* this list of conditions and the following disclaimer in the documentation
+* and/or other materials provided with the distribution.
+*
+* Subject to the terms and conditions of this license, each copyright holder
+* and contributor hereby grants to those receiving rights under this license
+* a perpetual, worldwide, non-exclusive, no-charge, royalty-free, irrevocable
+* (except for failure to satisfy the conditions of this license) patent license
+* to make, have made, use, offer to sell, sell, import, and otherwise transfer
+* this software, where such license applies only to those patent claims, already
+* acquired or hereafter acquired, licensable by such copyright holder or
+* contributor that are necessarily infringed by:
+*
+* (a) their Contribution(s) (the licensed copyrights of copyright holders and
+* non-copyrightable additions of contributors, in source or binary form) alone;
+* or
+*
+* (b) combination of their Contribution(s) with the work of authorship to which
+* such Contribution(s) was added by such copyright holder or contributor, if,
+* at the time the Contribution is added, such addition causes such combination
+* to be necessarily infringed. The patent license shall not apply to any other
+* combinations which include the Contribution.
+*
+* Except as expressly stated above, no rights or licenses from any copyright
+* holder or contributor is granted under this license, whether expressly, by
+* implication, estoppel or otherwise.
+*
+* DISCLAIMER
+*
+* THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+* AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+* IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+* DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS OR CONTRIBUTORS BE LIABLE
+* FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+* DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+* SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+* CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+* OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+* OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+*/
+
+use crate::inspect::{Compression, Entry, EntryKind, read};
+
+//================================================================
+
+use std::collections::HashMap;
+
+//================================================================
+
+/// squashfs magic number, "hsqs".
+const MAGIC: u64 = 0x7371_7368;
+/// deepest folder to walk into, so that a broken image cannot recurse forever.
+const DEPTH: usize = 64;
+/// most entries to list, so that a broken image whose folders list each other cannot grow forever.
+const LIMIT: usize = 1_000_000;
+
+/// metadata table of a squashfs image, decompressed as a whole.
+struct Table {
+    data: Vec<u8>,
+    /// offset of every block relative to the start of the table, mapped to its offset in data.
+    block: HashMap<u64, usize>,
+}
+
+impl Table {
+    /// decompress every metadata block from start to end.
+    fn new(image: &[u8], compression: Compression, start: u64, end: u64) -> anyhow::Result<Self> {
+        let mut data = Vec::new();
+        let mut block = HashMap::new();
+        let mut at = start;
+
+        while at < end {
+            let header = read(image, at as usize, 2)?;
+            let size = header & 0x7FFF;
+            let from = at as usize + 2;
+            let byte = image.get(from..from + size as usize).ok_or_else(|| {
+                anyhow::Error::msg("Inspect: File ends before it should, it may be cut short.")
+            })?;
+
+            block.insert(at - start, data.len());
+
+            // bit 15 tells that a block was stored as-is.
+            if header & 0x8000 != 0 {
+                data.extend_from_slice(byte);
+            } else {
+                data.extend(compression.decompress(byte)?);
+            }
+
+            at = from as u64 + size;
+        }
+
+        Ok(Self { data, block })
+    }
+
+    /// get the offset in data of a block and an offset within it.
+    fn at(&self, block: u64, offset: u64) -> anyhow::Result<usize> {
+        self.block
+            .get(&block)
+            .map(|x| x + offset as usize)
+            .ok_or_else(|| {
+                anyhow::Error::msg("Inspect: Squashfs image points to a block that is not there.")
+            })
+    }
+}
+
+/// squashfs image being walked.
+struct Image {
+    inode: Table,
+    folder: Table,
+}
+
+impl Image {
+    /// list a folder and every folder within it, given its inode reference.
+    fn walk(
+        &self,
+        reference: u64,
+        path: &str,
+        depth: usize,
+        list: &mut Vec<Entry>,
+    ) -> anyhow::Result<()> {
+        if depth > DEPTH {
+            return Err(anyhow::Error::msg(
+                "Inspect: Squashfs image nests folders too deep.",
+            ));
+        }
+
+        let inode = &self.inode.data;
+        let at = self.inode.at(reference >> 16, reference & 0xFFFF)?;
+
+        let (block, offset, size) = match read(inode, at, 2)? {
+            1 => (
+                read(inode, at + 16, 4)?,
+                read(inode, at + 26, 2)?,
+                read(inode, at + 24, 2)?,
+            ),
+            8 => (
+                read(inode, at + 24, 4)?,
+                read(inode, at + 34, 2)?,
+                read(inode, at + 20, 4)?,
+            ),
+            _ => {
+                return Err(anyhow::Error::msg(
+                    "Inspect: Squashfs entry is not a folder.",
+                ));
+            }
+        };
+
+        let folder = &self.folder.data;
+        let mut at = self.folder.at(block, offset)?;
+        // the size of a folder counts three bytes more than its listing.
+        let end = at + (size as usize).saturating_sub(3);
+
+        while at < end {
+            let count = read(folder, at, 4)? + 1;
+            let start = read(folder, at + 4, 4)?;
+            at += 12;
+
+            for _ in 0..count {
+                let offset = read(folder, at, 2)?;
+                let length = read(folder, at + 6, 2)? as usize + 1;
+                let name = folder.get(at + 8..at + 8 + length).ok_or_else(|| {
+                    anyhow::Error::msg("Inspect: File ends before it should, it may be cut short.")
+                })?;
+                let name = String::from_utf8_lossy(name);
+                at += 8 + length;
+
+                let path = if path.is_empty() {
+                    name.to_string()
+                } else {
+                    format!("{path}/{name}")
+                };
+                let reference = (start << 16) | offset;
+
+                let entry = self.entry(reference, path)?;
+                let kind = entry.kind;
+                let child = entry.path.clone();
+
+                list.push(entry);
+
+                if list.len() > LIMIT {
+                    return Err(anyhow::Error::msg(
+                        "Inspect: Squashfs image lists too many files, it may be corrupt.",
+                    ));
+                }
+
+                if kind == EntryKind::Folder {
+                    self.walk(reference, &child, depth + 1, list)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// read the inode of a single entry.
+    fn entry(&self, reference: u64, path: String) -> anyhow::Result<Entry> {
+        let inode = &self.inode.data;
+        let at = self.inode.at(reference >> 16, reference & 0xFFFF)?;
+        let mode = read(inode, at + 2, 2)? as u32;
+
+        Ok(match read(inode, at, 2)? {
+            1 => Entry::new(
+                path,
+                EntryKind::Folder,
+                mode,
+                read(inode, at + 24, 2)?,
+                None,
+            ),
+            8 => Entry::new(
+                path,
+                EntryKind::Folder,
+                mode,
+                read(inode, at + 20, 4)?,
+                None,
+            ),
+            2 => Entry::new(path, EntryKind::File, mode, read(inode, at + 28, 4)?, None),
+            9 => Entry::new(path, EntryKind::File, mode, read(inode, at + 24, 8)?, None),
+            3 | 10 => {
+                let size = read(inode, at + 20, 4)?;
+                let link = inode
+                    .get(at + 24..at + 24 + size as usize)
+                    .map(|x| String::from_utf8_lossy(x).to_string());
+
+                Entry::new(path, EntryKind::Link, mode, size, link)
+            }
+            _ => Entry::new(path, EntryKind::Other, mode, 0, None),
+        })
+    }
+}
+
+/// list every file in a squashfs image, along with the name of its compression.
+pub fn list(data: &[u8]) -> anyhow::Result<(String, Vec<Entry>)> {
+    if data.len() < 96 || read(data, 0, 4)? != MAGIC {
+        return Err(anyhow::Error::msg("Inspect: No squashfs image found."));
+    }
+
+    let (compression, name) = match read(data, 20, 2)? {
+        1 => (Compression::Zlib, "gzip"),
+        4 => (Compression::Xz, "xz"),
+        6 => (Compression::Zstd, "zstd"),
+        kind => {
+            return Err(anyhow::Error::msg(format!(
+                "Inspect: Squashfs compression {kind} is not supported."
+            )));
+        }
+    };
+
+    let root = read(data, 32, 8)?;
+    let inode_table = read(data, 64, 8)?;
+    let folder_table = read(data, 72, 8)?;
+
+    // the folder table ends where the first table after it starts.
+    let folder_end = [40, 48, 56, 80, 88]
+        .iter()
+        .map(|at| read(data, *at, 8))
+        .collect::<anyhow::Result<Vec<_>>>()?
+        .into_iter()
+        .filter(|x| *x > folder_table && *x != u64::MAX)
+        .min()
+        .unwrap_or(folder_table);
+
+    let image = Image {
+        inode: Table::new(data, compression, inode_table, folder_table)?,
+        folder: Table::new(data, compression, folder_table, folder_end)?,
+    };
+
+    let mut list = Vec::new();
+    image.walk(root, "", 0, &mut list)?;
+
+    Ok((name.to_string(), list))
+}
+
+//================================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// append a little-endian integer of the given size.
+    fn push(data: &mut Vec<u8>, value: u64, size: usize) {
+        data.extend_from_slice(&value.to_le_bytes()[..size]);
+    }
+
+    /// append a metadata block stored as-is.
+    fn block(data: &mut Vec<u8>, byte: &[u8]) {
+        push(data, 0x8000 | byte.len() as u64, 2);
+        data.extend_from_slice(byte);
+    }
+
+    /// append an inode header.
+    fn header(data: &mut Vec<u8>, kind: u64, mode: u64, number: u64) {
+        for (value, size) in [(kind, 2), (mode, 2), (0, 2), (0, 2), (0, 4), (number, 4)] {
+            push(data, value, size);
+        }
+    }
+
+    #[test]
+    fn list() {
+        // inode table: a file, a link to it and the root folder.
+        let mut inode = Vec::new();
+        header(&mut inode, 2, 0o644, 1);
+        for (value, size) in [(0, 4), (u64::from(u32::MAX), 4), (0, 4), (5, 4), (5, 4)] {
+            push(&mut inode, value, size);
+        }
+        let link = inode.len() as u64;
+        header(&mut inode, 3, 0o777, 2);
+        push(&mut inode, 1, 4);
+        push(&mut inode, 5, 4);
+        inode.extend_from_slice(b"hello");
+        let root = inode.len() as u64;
+
+        // folder table: the root folder listing.
+        let mut folder = Vec::new();
+        for (value, size) in [(1, 4), (0, 4), (1, 4)] {
+            push(&mut folder, value, size);
+        }
+        for (offset, number, kind, name) in [(0, 0, 2, "hello"), (link, 1, 3, "link")] {
+            for (value, size) in [
+                (offset, 2),
+                (number, 2),
+                (kind, 2),
+                (name.len() as u64 - 1, 2),
+            ] {
+                push(&mut folder, value, size);
+            }
+            folder.extend_from_slice(name.as_bytes());
+        }
+
+        header(&mut inode, 1, 0o755, 3);
+        for (value, size) in [(0, 4), (2, 4), (folder.len() as u64 + 3, 2), (0, 2), (4, 4)] {
+            push(&mut inode, value, size);
+        }
+
+        let inode_table = 96;
+        let folder_table = inode_table + 2 + inode.len() as u64;
+        let id_table = folder_table + 2 + folder.len() as u64;
+
+        let mut data = Vec::new();
+        for (value, size) in [
+            (MAGIC, 4),
+            (3, 4),
+            (0, 4),
+            (131_072, 4),
+            (0, 4),
+            (1, 2),
+            (17, 2),
+            (0, 2),
+            (1, 2),
+            (4, 2),
+            (0, 2),
+        ] {
+            push(&mut data, value, size);
+        }
+        push(&mut data, root, 8);
+        push(&mut data, id_table + 8, 8);
+        for value in [
+            id_table,
+            u64::MAX,
+            inode_table,
+            folder_table,
+            u64::MAX,
+            u64::MAX,
+        ] {
+            push(&mut data, value, 8);
+        }
+        assert_eq!(data.len(), 96);
+
+        block(&mut data, &inode);
+        block(&mut data, &folder);
+        push(&mut data, id_table, 8);
+
+        let (compression, list) = super::list(&data).unwrap();
+
+        assert_eq!(compression, "gzip");
+        assert_eq!(list.len(), 2);
+        assert_eq!(
+            (
+                list[0].path.as_str(),
+                list[0].kind,
+                list[0].mode.as_str(),
+                list[0].size
+            ),
+            ("hello", EntryKind::File, "0644", 5)
+        );
+        assert_eq!(list[1].kind, EntryKind::Link);
+        assert_eq!(list[1].link.as_deref(), Some("hello"));
+    }
+}
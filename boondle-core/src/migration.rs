@@ -0,0 +1,191 @@
+/*
+* Copyright (c) 2025 luxreduxdelux
+*
+* Redistribution and use in source and binary forms, with or without
+* modification, are permitted provided that the following conditions are met:
+*
+* 1. Redistributions of source code must retain the above copyright notice,
+* this list of conditions and the following disclaimer.
+*
+* 2. Redistributions in binary form must reproduce the above copyright notice,
+* this list of conditions and the following disclaimer in the documentation
+* and/or other materials provided with the distribution.
+*
+* Subject to the terms and conditions of this license, each copyright holder
+* and contributor hereby grants to those receiving rights under this license
+* a perpetual, worldwide, non-exclusive, no-charge, royalty-free, irrevocable
+* (except for failure to satisfy the conditions of this license) patent license
+* to make, have made, use, offer to sell, sell, import, and otherwise transfer
+* this software, where such license applies only to those patent claims, already
+* acquired or hereafter acquired, licensable by such copyright holder or
+* contributor that are necessarily infringed by:
+*
+* (a) their Contribution(s) (the licensed copyrights of copyright holders and
+* non-copyrightable additions of contributors, in source or binary form) alone;
+* or
+*
+* (b) combination of their Contribution(s) with the work of authorship to which
+* such Contribution(s) was added by such copyright holder or contributor, if,
+* at the time the Contribution is added, such addition causes such combination
+* to be necessarily infringed. The patent license shall not apply to any other
+* combinations which include the Contribution.
+*
+* Except as expressly stated above, no rights or licenses from any copyright
+* holder or contributor is granted under this license, whether expressly, by
+* implication, estoppel or otherwise.
+*
+* DISCLAIMER
+*
+* THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+* AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+* IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+* DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS OR CONTRIBUTORS BE LIABLE
+* FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+* DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+* SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+* CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+* OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+* OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+*/
+
+use serde_json::Value;
+
+//================================================================
+
+/// the project file format version written by this build.
+pub const FORMAT_VERSION: u64 = 1;
+
+/// every migration step, indexed by the format version it upgrades from.
+const LIST_MIGRATION: [fn(&mut Value) -> anyhow::Result<()>; FORMAT_VERSION as usize] =
+    [migrate_0_to_1];
+
+/// get the format version of a project file. files without one are version 0.
+pub fn version(value: &Value) -> anyhow::Result<u64> {
+    match value.get("format_version") {
+        None => Ok(0),
+        Some(version) => version.as_u64().ok_or(anyhow::Error::msg(
+            "Migration: \"format_version\" must be a positive integer.",
+        )),
+    }
+}
+
+/// upgrade a project file to the current format version, one step at a time.
+pub fn migrate(value: &mut Value) -> anyhow::Result<()> {
+    let current = version(value)?;
+
+    if current > FORMAT_VERSION {
+        return Err(anyhow::Error::msg(format!(
+            "Migration: Project format version {current} is newer than the supported version {FORMAT_VERSION}, please update Boondle."
+        )));
+    }
+
+    for from in current..FORMAT_VERSION {
+        LIST_MIGRATION[from as usize](value)?;
+
+        if let Some(object) = value.as_object_mut() {
+            object.insert("format_version".to_string(), Value::from(from + 1));
+        }
+    }
+
+    Ok(())
+}
+
+/// version 0 wrote `null` for empty path fields, replace every one with an empty string.
+fn migrate_0_to_1(value: &mut Value) -> anyhow::Result<()> {
+    let object = value.as_object_mut().ok_or(anyhow::Error::msg(
+        "Migration: Project file must be an object.",
+    ))?;
+
+    if let Some(Value::Object(meta)) = object.get_mut("meta") {
+        null_to_string(meta);
+    }
+
+    for list in ["compile", "package"] {
+        if let Some(Value::Array(list)) = object.get_mut(list) {
+            for export in list {
+                if let Value::Object(export) = export {
+                    null_to_string(export);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn null_to_string(object: &mut serde_json::Map<String, Value>) {
+    for (_, value) in object.iter_mut() {
+        if value.is_null() {
+            *value = Value::String(String::default());
+        }
+    }
+}
+
+//================================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::project::Project;
+
+    const FIXTURE_0: &str = include_str!("../tests/fixture/project_v0.json");
+    const FIXTURE_1: &str = include_str!("../tests/fixture/project_v1.json");
+
+    #[test]
+    fn migrate_version_0() {
+        let mut value: Value = serde_json::from_str(FIXTURE_0).unwrap();
+
+        assert_eq!(version(&value).unwrap(), 0);
+
+        migrate(&mut value).unwrap();
+
+        assert_eq!(version(&value).unwrap(), FORMAT_VERSION);
+        assert_eq!(value["package"][0]["script"], "");
+        assert_eq!(value["package"][1]["script_prior"], "");
+        assert_eq!(value["package"][1]["script_after"], "");
+
+        let project: Project = serde_json::from_value(value).unwrap();
+
+        assert_eq!(project.format_version, FORMAT_VERSION);
+        assert_eq!(project.package.len(), 3);
+    }
+
+    #[test]
+    fn migrate_version_current() {
+        let mut value: Value = serde_json::from_str(FIXTURE_1).unwrap();
+        let clone = value.clone();
+
+        migrate(&mut value).unwrap();
+
+        assert_eq!(value, clone);
+        assert!(serde_json::from_value::<Project>(value).is_ok());
+    }
+
+    #[test]
+    fn migrate_version_newer() {
+        let mut value: Value = serde_json::from_str(FIXTURE_1).unwrap();
+        value["format_version"] = Value::from(FORMAT_VERSION + 1);
+
+        assert!(migrate(&mut value).is_err());
+    }
+
+    #[test]
+    fn migrate_file_backup() {
+        let path = std::env::temp_dir().join(format!("boondle_migrate_{}", std::process::id()));
+        std::fs::create_dir_all(&path).unwrap();
+        std::fs::write(path.join("project.json"), FIXTURE_0).unwrap();
+
+        assert!(Project::migrate(path.clone()).unwrap());
+        assert!(!Project::migrate(path.clone()).unwrap());
+
+        let backup = std::fs::read_to_string(path.join("project.json.bak")).unwrap();
+        let result: Value =
+            serde_json::from_str(&std::fs::read_to_string(path.join("project.json")).unwrap())
+                .unwrap();
+
+        assert_eq!(backup, FIXTURE_0);
+        assert_eq!(version(&result).unwrap(), FORMAT_VERSION);
+
+        std::fs::remove_dir_all(path).unwrap();
+    }
+}
@@ -0,0 +1,1478 @@
+/*
+* Copyright (c) 2025 luxreduxdelux
+*
+* Redistribution and use in source and binary forms, with or without
+* modification, are permitted provided that the following conditions are met:
+*
+* 1. Redistributions of source code must retain the above copyright notice,
+* this list of conditions and the following disclaimer.
+*
+* 2. Redistributions in binary form must reproduce the above copyright notice,
+* this list of conditions and the following disclaimer in the documentation
+* and/or other materials provided with the distribution.
+*
+* Subject to the terms and conditions of this license, each copyright holder
+* and contributor hereby grants to those receiving rights under this license
+* a perpetual, worldwide, non-exclusive, no-charge, royalty-free, irrevocable
+* (except for failure to satisfy the conditions of this license) patent license
+* to make, have made, use, offer to sell, sell, import, and otherwise transfer
+* this software, where such license applies only to those patent claims, already
+* acquired or hereafter acquired, licensable by such copyright holder or
+* contributor that are necessarily infringed by:
+*
+* (a) their Contribution(s) (the licensed copyrights of copyright holders and
+* non-copyrightable additions of contributors, in source or binary form) alone;
+* or
+*
+* (b) combination of their Contribution(s) with the work of authorship to which
+* such Contribution(s) was added by such copyright holder or contributor, if,
+* at the time the Contribution is added, such addition causes such combination
+* to be necessarily infringed. The patent license shall not apply to any other
+* combinations which include the Contribution.
+*
+* Except as expressly stated above, no rights or licenses from any copyright
+* holder or contributor is granted under this license, whether expressly, by
+* implication, estoppel or otherwise.
+*
+* DISCLAIMER
+*
+* THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+* AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+* IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+* DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS OR CONTRIBUTORS BE LIABLE
+* FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+* DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+* SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+* CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+* OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+* OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+*/
+
+use crate::{
+    exporter::{
+        artifact::{Artifact, ArtifactKind},
+        export::{Export, kind, label},
+        plan::Plan,
+        sign::Signing,
+    },
+    format::Format,
+    history::Run,
+    migration::{self, FORMAT_VERSION},
+    scheduler::Scheduler,
+    schema::ExportSchema,
+    template::Template,
+};
+
+//================================================================
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ffi::OsStr,
+    path::{Component, Path, PathBuf},
+    process::Command,
+};
+
+//================================================================
+
+#[derive(Default, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Meta {
+    #[serde(skip)]
+    pub path: PathBuf,
+    #[serde(skip)]
+    pub template: Template,
+    #[serde(skip)]
+    pub artifact: Vec<Artifact>,
+    #[serde(skip)]
+    pub stage: PathBuf,
+    #[serde(skip)]
+    pub output: PathBuf,
+    #[serde(skip)]
+    pub output_name: String,
+    #[serde(skip)]
+    pub signing: Signing,
+    /// SOURCE_DATE_EPOCH of the run, if known, for a reproducible build.
+    #[serde(skip)]
+    pub epoch: Option<u64>,
+    /// Application name, also used for the binary and package name.
+    pub name: String,
+    /// Path to the application icon.
+    pub icon: String,
+    /// Short description of the application.
+    pub info: String,
+    /// Author or maintainer of the application.
+    pub from: String,
+    /// Application version.
+    pub version: String,
+    /// Generic name of the application, e.g. "Web Browser".
+    pub name_generic: String,
+    /// Long description of the application.
+    pub comment: String,
+    /// Desktop entry category.
+    pub category: String,
+    /// Desktop entry key-words.
+    pub key_word: String,
+    /// Whether the application runs in a terminal.
+    pub command_line: bool,
+    /// Home-page of the application.
+    #[serde(default)]
+    pub homepage: String,
+    /// License of the application, as an SPDX expression.
+    #[serde(default)]
+    pub license: String,
+    /// Path to a Cargo.toml. Empty name, info, from, version, homepage and license fields are
+    /// filled in from its [package] table, and {cargo.KEY} placeholders read from it.
+    #[serde(default)]
+    pub cargo: String,
+}
+
+impl Meta {
+    /// every string field, in the order of `field_mut`, by placeholder name.
+    pub const LIST_FIELD: [&'static str; 11] = [
+        "name",
+        "icon",
+        "info",
+        "from",
+        "version",
+        "name_generic",
+        "comment",
+        "category",
+        "key_word",
+        "homepage",
+        "license",
+    ];
+
+    /// every field filled in from the Cargo manifest when left empty, with its manifest key.
+    const LIST_CARGO: [(&'static str, &'static str); 6] = [
+        ("name", "name"),
+        ("info", "description"),
+        ("from", "authors"),
+        ("version", "version"),
+        ("homepage", "homepage"),
+        ("license", "license"),
+    ];
+
+    /// package file name, for a step with no name and with a name.
+    const OUTPUT_NAME: &str = "{name}_{version}_{arch}";
+    const OUTPUT_NAME_STEP: &str = "{name}-{step}_{version}_{arch}";
+
+    const FILE_DESKTOP: &str = r#"[Desktop Entry]
+Name={name}
+{icon}
+Exec=/usr/bin/{name}
+Type=Application
+Categories=Utility;
+"#;
+
+    pub fn create_desktop_file(&self, icon_root: bool) -> String {
+        let icon = if icon_root {
+            format!("Icon=/{}-icon", self.name)
+        } else {
+            format!("Icon=/usr/share/icons/{}-icon", self.name)
+        };
+
+        let mut file = Self::FILE_DESKTOP.to_string();
+        file = file.replace("{name}", &self.name);
+        file = file.replace("{icon}", &icon);
+        //file = file.replace("{name_generic}", &self.name_generic);
+        //file = file.replace(
+        //    "{command_line}",
+        //    if self.command_line { "true" } else { "false" },
+        //);
+        //file = file.replace("{comment}", &self.comment);
+        //file = file.replace("{category}", &self.category);
+        //file = file.replace("{key_word}", &self.key_word);
+
+        file
+    }
+
+    /// get every string field, in the order of `LIST_FIELD`.
+    pub fn field_mut(&mut self) -> [&mut String; 11] {
+        [
+            &mut self.name,
+            &mut self.icon,
+            &mut self.info,
+            &mut self.from,
+            &mut self.version,
+            &mut self.name_generic,
+            &mut self.comment,
+            &mut self.category,
+            &mut self.key_word,
+            &mut self.homepage,
+            &mut self.license,
+        ]
+    }
+
+    /// find the artifact of a step, for an architecture if given.
+    pub fn artifact_find(
+        &self,
+        step: &str,
+        kind: ArtifactKind,
+        architecture: Option<&str>,
+    ) -> anyhow::Result<&Artifact> {
+        let list: Vec<&Artifact> = self
+            .artifact
+            .iter()
+            .filter(|x| x.step == step && x.kind == kind)
+            .filter(|x| architecture.is_none_or(|architecture| x.architecture_match(architecture)))
+            .collect();
+
+        match list.as_slice() {
+            [artifact] => Ok(artifact),
+            [] => Err(anyhow::Error::msg(format!(
+                "Artifact: Step \"{step}\" has no {kind} artifact{}.",
+                architecture
+                    .map(|x| format!(" for architecture \"{x}\""))
+                    .unwrap_or_default()
+            ))),
+            _ => Err(anyhow::Error::msg(format!(
+                "Artifact: Step \"{step}\" has more than one {kind} artifact{}, pick one in the step.",
+                architecture
+                    .map(|x| format!(" for architecture \"{x}\""))
+                    .unwrap_or_default()
+            ))),
+        }
+    }
+
+    /// get the binary to package: the binary artifact of a compile step if given, the binary path otherwise.
+    pub fn binary(
+        &self,
+        step: &str,
+        binary: &str,
+        architecture: &str,
+    ) -> anyhow::Result<Option<PathBuf>> {
+        if !step.is_empty() {
+            let artifact = self.artifact_find(step, ArtifactKind::Binary, Some(architecture))?;
+
+            return Ok(Some(artifact.path.clone()));
+        }
+
+        let binary = self.template.format(binary)?;
+
+        if binary.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(self.resolve(&binary)))
+        }
+    }
+
+    /// get the path of a step's package: the step's own file name template, or else the project's,
+    /// in the project's output folder.
+    pub fn output_path(
+        &self,
+        file: &str,
+        step: &str,
+        architecture: &str,
+        extension: &str,
+    ) -> anyhow::Result<PathBuf> {
+        let file = if !file.is_empty() {
+            file
+        } else if !self.output_name.is_empty() {
+            &self.output_name
+        } else if step.is_empty() {
+            Self::OUTPUT_NAME
+        } else {
+            Self::OUTPUT_NAME_STEP
+        };
+
+        let file = self
+            .template
+            .with("arch", architecture)
+            .with("step", step)
+            .format(file)?;
+
+        Ok(self.output.join(format!("{file}.{extension}")))
+    }
+
+    /// create a command to run, with SOURCE_DATE_EPOCH set if it is known.
+    pub fn command(&self, program: impl AsRef<OsStr>) -> Command {
+        let mut command = Command::new(program);
+
+        if let Some(epoch) = self.epoch {
+            command.env("SOURCE_DATE_EPOCH", epoch.to_string());
+        }
+
+        command
+    }
+
+    /// get the time every file of a run is dated to, for a reproducible build: SOURCE_DATE_EPOCH
+    /// if set, the time of the last git commit of the project folder otherwise.
+    pub fn epoch(&self) -> anyhow::Result<Option<u64>> {
+        match Self::epoch_parse(std::env::var("SOURCE_DATE_EPOCH").ok().as_deref())? {
+            Some(epoch) => Ok(Some(epoch)),
+            None => Ok(self.epoch_git()),
+        }
+    }
+
+    /// parse the value of SOURCE_DATE_EPOCH, if set.
+    fn epoch_parse(epoch: Option<&str>) -> anyhow::Result<Option<u64>> {
+        let Some(epoch) = epoch else {
+            return Ok(None);
+        };
+
+        epoch.trim().parse().map(Some).map_err(|_| {
+            anyhow::Error::msg(format!(
+                "Reproducible: SOURCE_DATE_EPOCH \"{epoch}\" is not a number of seconds."
+            ))
+        })
+    }
+
+    /// get the time of the last git commit of the project folder, if there is one.
+    fn epoch_git(&self) -> Option<u64> {
+        // not a git repository, or no commit yet.
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&self.path)
+            .args(["log", "-1", "--format=%ct"])
+            .output();
+
+        output
+            .ok()
+            .filter(|x| x.status.success())
+            .and_then(|x| String::from_utf8_lossy(&x.stdout).trim().parse().ok())
+    }
+
+    /// resolve a path from the project file against the project folder.
+    pub fn resolve(&self, path: &str) -> PathBuf {
+        self.path.join(path)
+    }
+
+    /// make a path relative to the project folder, if both share a common ancestor.
+    pub fn relative(&self, path: &str) -> String {
+        let path = Path::new(path);
+
+        if path.is_relative() {
+            return path.display().to_string();
+        }
+
+        let mut base = self.path.components().peekable();
+        let mut file = path.components().peekable();
+        let mut share = 0;
+
+        while let (Some(a), Some(b)) = (base.peek(), file.peek())
+            && a == b
+        {
+            base.next();
+            file.next();
+            share += 1;
+        }
+
+        // only the root folder is shared, keep the path absolute.
+        if share <= 1 {
+            return path.display().to_string();
+        }
+
+        let mut result = PathBuf::new();
+
+        for component in base {
+            if let Component::Normal(_) = component {
+                result.push("..");
+            }
+        }
+
+        for component in file {
+            result.push(component);
+        }
+
+        result.display().to_string()
+    }
+}
+
+#[derive(Default, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    /// Project fields to override, by name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    #[schemars(extend("propertyNames" = { "enum": Meta::LIST_FIELD }))]
+    pub meta: BTreeMap<String, String>,
+    /// Variables to set, over the project's own.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub variable: BTreeMap<String, String>,
+    /// Compile or package steps to enable or disable, by step name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub enable: BTreeMap<String, bool>,
+}
+
+impl Profile {
+    /// check if a step is to be run, with an optional profile on top.
+    pub fn step_enable(profile: Option<&Self>, step: &dyn Export) -> bool {
+        profile
+            .and_then(|profile| profile.enable.get(step.get_name()))
+            .copied()
+            .unwrap_or(step.get_enable())
+    }
+}
+
+#[derive(Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Project {
+    #[serde(skip)]
+    pub file: PathBuf,
+    #[serde(skip)]
+    pub profile_active: String,
+    #[serde(skip)]
+    pub artifact: Vec<Artifact>,
+    #[serde(skip)]
+    pub schedule_compile: Option<Scheduler>,
+    #[serde(skip)]
+    pub schedule_package: Option<Scheduler>,
+    /// Path or URL to the JSON Schema of this file, for editor validation.
+    #[serde(rename = "$schema", default, skip_serializing_if = "String::is_empty")]
+    pub schema: String,
+    /// Project file format version.
+    pub format_version: u64,
+    /// Folder package steps stage their files into, relative to the project folder. Every run
+    /// stages into a fresh folder inside it. Empty for "boondle_stage".
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub stage: String,
+    /// Folder packages are written to, relative to the project folder. Empty for the project folder.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub output: String,
+    /// Package file name, without extension, for every package step that doesn't set its own file.
+    /// Supports {step} and {arch}, too. Empty for "{name}-{step}_{version}_{arch}", or
+    /// "{name}_{version}_{arch}" for a step with no name.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub output_name: String,
+    /// Key to sign packages with, used by the Sign step and to embed a signature into every
+    /// AppImage.
+    #[serde(default, skip_serializing_if = "Signing::is_empty")]
+    pub signing: Signing,
+    /// Most steps to run at once. 0 for one per CPU core.
+    #[serde(default, skip_serializing_if = "Project::job_default")]
+    pub job: usize,
+    /// Project metadata.
+    pub meta: Meta,
+    /// User-defined variables, used as {key} in any string field.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub variable: BTreeMap<String, String>,
+    /// Named sets of overrides, e.g. for a nightly and a stable channel.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profile: BTreeMap<String, Profile>,
+    /// Compile steps.
+    #[schemars(with = "Vec<ExportSchema>")]
+    pub compile: Vec<Box<dyn Export>>,
+    /// Package steps.
+    #[schemars(with = "Vec<ExportSchema>")]
+    pub package: Vec<Box<dyn Export>>,
+}
+
+impl Project {
+    /// staging folder, in the project folder, unless the project sets its own.
+    pub const STAGE: &'static str = "boondle_stage";
+
+    /// create a new project in a folder, writing its project file.
+    pub fn create(path: &Path) -> anyhow::Result<Self> {
+        let path = std::fs::canonicalize(path)?;
+
+        let mut result = Self {
+            file: path.join(Format::default().file_name()),
+            format_version: FORMAT_VERSION,
+            meta: Meta {
+                path,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        result.save()?;
+
+        Ok(result)
+    }
+
+    pub fn compile(&mut self) -> anyhow::Result<()> {
+        let meta = self.meta_resolve()?;
+        let profile = self.profile_get()?.cloned();
+
+        let problem = Self::validate(&self.compile, &meta, profile.as_ref());
+        Self::validate_report(problem)?;
+
+        self.schedule_compile = Some(Scheduler::new(
+            &mut self.compile,
+            meta,
+            self.job,
+            profile.as_ref(),
+        )?);
+        self.poll()?;
+
+        Ok(())
+    }
+
+    pub fn package(&mut self) -> anyhow::Result<()> {
+        let meta = self.meta_package()?;
+        let profile = self.profile_get()?.cloned();
+
+        let mut problem = Self::validate(&self.package, &meta, profile.as_ref());
+        problem.extend(self.artifact_check(&meta, profile.as_ref()));
+        problem.extend(self.output_check(&meta, profile.as_ref()));
+        Self::validate_report(problem)?;
+
+        self.schedule_package = Some(Scheduler::new(
+            &mut self.package,
+            meta,
+            self.job,
+            profile.as_ref(),
+        )?);
+        self.poll()?;
+
+        Ok(())
+    }
+
+    /// get everything the enabled steps of the compile or package stage would do, with every
+    /// template resolved, without running anything or touching the filesystem.
+    pub fn preview(&self, package: bool) -> anyhow::Result<Vec<Plan>> {
+        let (meta, list) = if package {
+            (self.meta_package()?, &self.package)
+        } else {
+            (self.meta_resolve()?, &self.compile)
+        };
+        let profile = self.profile_get()?;
+        let mut result = Vec::new();
+
+        for step in list {
+            if Profile::step_enable(profile, step.as_ref()) {
+                let meta = Self::meta_rank(list, &meta, profile, step.get_rank())?;
+                let mut plan = step.plan(&meta)?;
+                plan.step = step.get_name().to_string();
+                plan.kind = kind(step.as_ref());
+
+                result.push(plan);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// get the project metadata a step of a rank sees: with the artifacts every enabled step of a
+    /// lower rank declares, as if they had all been run.
+    fn meta_rank(
+        list: &[Box<dyn Export>],
+        meta: &Meta,
+        profile: Option<&Profile>,
+        rank: u32,
+    ) -> anyhow::Result<Meta> {
+        let mut result = meta.clone();
+
+        for step in list {
+            if Profile::step_enable(profile, step.as_ref()) && step.get_rank() < rank {
+                let meta = Self::meta_rank(list, meta, profile, step.get_rank())?;
+                result.artifact.extend(step.get_artifact(&meta)?);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// resolve the project metadata for the package stage, with the artifacts every named compile
+    /// step declares.
+    fn meta_package(&self) -> anyhow::Result<Meta> {
+        let mut meta = self.meta_resolve()?;
+
+        meta.artifact = self.artifact_declare(&meta)?;
+        meta.stage = self.stage_fresh(&meta)?;
+
+        // expose the binaries of every named compile step, the first one being the default.
+        let mut default = BTreeSet::new();
+
+        for artifact in &meta.artifact {
+            if artifact.kind == ArtifactKind::Binary {
+                let key = format!("compile.{}.binary", artifact.step);
+                let path = artifact.path.display().to_string();
+
+                if default.insert(key.clone()) {
+                    meta.template.insert(&key, &path);
+                }
+
+                meta.template
+                    .insert(&format!("{key}.{}", artifact.architecture), &path);
+            }
+        }
+
+        Ok(meta)
+    }
+
+    /// get the folder every run stages into.
+    pub fn stage_folder(&self, meta: &Meta) -> anyhow::Result<PathBuf> {
+        let stage = meta.template.format(&self.stage)?;
+
+        Ok(meta.resolve(if stage.is_empty() {
+            Self::STAGE
+        } else {
+            &stage
+        }))
+    }
+
+    /// get a fresh folder for a run to stage into, named after the time the run started.
+    fn stage_fresh(&self, meta: &Meta) -> anyhow::Result<PathBuf> {
+        let folder = self.stage_folder(meta)?;
+        let name = meta.template.format("{date}_{time}")?;
+        let mut path = folder.join(&name);
+
+        // runs started in the same second get a counter.
+        for i in 1.. {
+            if !path.exists() {
+                break;
+            }
+
+            path = folder.join(format!("{name}_{i}"));
+        }
+
+        Ok(path)
+    }
+
+    /// check if a folder in the staging folder was made by a run: either named after the time the
+    /// run started, e.g. "2024-01-31_120000" or "2024-01-31_120000_1", or kept by a reproducibility check.
+    fn stage_run(name: &str) -> bool {
+        if name == "reproducible" {
+            return true;
+        }
+
+        // compare the shape of the name, with every digit as 0.
+        let shape: String = name
+            .chars()
+            .map(|x| if x.is_ascii_digit() { '0' } else { x })
+            .collect();
+
+        match shape.strip_prefix("0000-00-00_000000") {
+            Some("") => true,
+            Some(counter) => {
+                counter.len() > 1
+                    && counter[1..].bytes().all(|x| x == b'0')
+                    && counter.starts_with('_')
+            }
+            None => false,
+        }
+    }
+
+    /// remove every folder a run staged into, and the package every package step builds if asked
+    /// to, returning every path removed.
+    pub fn clean(&mut self, artifact: bool) -> anyhow::Result<Vec<PathBuf>> {
+        let meta = self.meta_resolve()?;
+        let stage = self.stage_folder(&meta)?;
+
+        if meta.path.starts_with(&stage) {
+            return Err(anyhow::Error::msg(format!(
+                "Clean: Staging folder \"{}\" holds the project itself, it will not be removed.",
+                stage.display()
+            )));
+        }
+
+        // only the folders of past runs are removed, anything else in the staging folder is left alone.
+        let mut list: Vec<PathBuf> = std::fs::read_dir(&stage)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|entry| {
+                entry.path().is_dir() && Self::stage_run(&entry.file_name().to_string_lossy())
+            })
+            .map(|entry| entry.path())
+            .collect();
+
+        // folders staged into before the staging folder could be set.
+        list.push(meta.path.join("boondle_debian"));
+        list.push(meta.path.join("boondle_app_image"));
+
+        if artifact {
+            let profile = self.profile_get()?;
+
+            // package steps see what the compile steps declare, as when they run, so that e.g. the
+            // signatures of a compile script's packages are removed, but not the packages.
+            let mut meta = meta.clone();
+            meta.artifact = self.artifact_declare(&meta)?;
+
+            for package in &self.package {
+                let meta = Self::meta_rank(&self.package, &meta, profile, package.get_rank())?;
+                list.extend(package.get_artifact(&meta)?.into_iter().map(|x| x.path));
+            }
+        }
+
+        let mut result = Vec::new();
+
+        for path in list {
+            if path.is_dir() {
+                std::fs::remove_dir_all(&path)?;
+            } else if path.is_file() {
+                std::fs::remove_file(&path)?;
+            } else {
+                continue;
+            }
+
+            result.push(path);
+        }
+
+        self.artifact.retain(|x| x.path.exists());
+
+        Ok(result)
+    }
+
+    /// advance the compile and package steps being run. once a stage is done, its artifacts are
+    /// recorded and the run is written to the project's history. returns true once no step is left to run.
+    pub fn poll(&mut self) -> anyhow::Result<bool> {
+        if let Some(schedule) = self
+            .schedule_compile
+            .take_if(|schedule| schedule.poll(&mut self.compile))
+        {
+            self.finish(schedule, false)?;
+        }
+
+        if let Some(schedule) = self
+            .schedule_package
+            .take_if(|schedule| schedule.poll(&mut self.package))
+        {
+            self.finish(schedule, true)?;
+        }
+
+        Ok(self.schedule_compile.is_none() && self.schedule_package.is_none())
+    }
+
+    /// record the outcome of a finished stage. its artifacts are kept even if the history can't be written.
+    fn finish(&mut self, schedule: Scheduler, package: bool) -> anyhow::Result<()> {
+        let (stage, list) = if package {
+            ("package", &mut self.package)
+        } else {
+            ("compile", &mut self.compile)
+        };
+
+        let write = Run::write(stage, &self.profile_active, &schedule, list);
+
+        Self::artifact_add(&mut self.artifact, schedule.artifact);
+
+        write.map(|_| ())
+    }
+
+    /// cancel every compile and package step being run.
+    pub fn cancel(&mut self) {
+        if let Some(schedule) = &mut self.schedule_compile {
+            schedule.cancel(&mut self.compile);
+        }
+
+        if let Some(schedule) = &mut self.schedule_package {
+            schedule.cancel(&mut self.package);
+        }
+    }
+
+    /// get the artifacts every named compile step declares.
+    fn artifact_declare(&self, meta: &Meta) -> anyhow::Result<Vec<Artifact>> {
+        let mut result = Vec::new();
+
+        for compile in &self.compile {
+            if !compile.get_name().is_empty() {
+                result.extend(compile.get_artifact(meta)?);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// check that every artifact an enabled package step uses exists and is up-to-date, returning every problem found.
+    fn artifact_check(&self, meta: &Meta, profile: Option<&Profile>) -> Vec<String> {
+        let mut problem = Vec::new();
+
+        for package in &self.package {
+            if !Profile::step_enable(profile, package.as_ref()) {
+                continue;
+            }
+
+            for reference in package.get_reference() {
+                let Some(compile) = self.compile.iter().find(|x| x.get_name() == reference) else {
+                    problem.push(format!("Package: No compile step named \"{reference}\"."));
+                    continue;
+                };
+
+                let source = match compile.get_source(meta) {
+                    Ok(source) => source,
+                    Err(error) => {
+                        problem.push(error.to_string());
+                        continue;
+                    }
+                };
+
+                for artifact in meta.artifact.iter().filter(|x| x.step == reference) {
+                    if let Err(error) = artifact.check(&source) {
+                        problem.push(error.to_string());
+                    }
+                }
+            }
+        }
+
+        problem.dedup();
+        problem
+    }
+
+    /// check that no two enabled package steps would write the same file.
+    fn output_check(&self, meta: &Meta, profile: Option<&Profile>) -> Vec<String> {
+        let mut problem = Vec::new();
+        let mut output: BTreeMap<PathBuf, String> = BTreeMap::new();
+
+        for package in &self.package {
+            if !Profile::step_enable(profile, package.as_ref()) {
+                continue;
+            }
+
+            // a step whose file name can't be resolved reports it in its own validation.
+            let Ok(artifact) = package.get_artifact(meta) else {
+                continue;
+            };
+
+            let name = if package.get_name().is_empty() {
+                kind(package.as_ref())
+            } else {
+                format!("\"{}\"", package.get_name())
+            };
+
+            for artifact in artifact {
+                if let Some(other) = output.get(&artifact.path) {
+                    problem.push(format!(
+                        "Output: Steps {other} and {name} would both write \"{}\".",
+                        artifact.path.display()
+                    ));
+                } else {
+                    output.insert(artifact.path, name.clone());
+                }
+            }
+        }
+
+        problem
+    }
+
+    /// check every enabled step of a stage before any of them is run, returning every problem found.
+    fn validate(list: &[Box<dyn Export>], meta: &Meta, profile: Option<&Profile>) -> Vec<String> {
+        list.iter()
+            .filter(|step| Profile::step_enable(profile, step.as_ref()))
+            .flat_map(|step| {
+                let label = label(step.as_ref());
+
+                step.validate(meta)
+                    .into_iter()
+                    .map(move |x| format!("[{label}] {x}"))
+            })
+            .collect()
+    }
+
+    /// turn the problems found by validation into a single error, if any.
+    fn validate_report(problem: Vec<String>) -> anyhow::Result<()> {
+        match problem.len() {
+            0 => Ok(()),
+            1 => Err(anyhow::Error::msg(format!(
+                "Validation: 1 problem found, nothing was run.\n- {}",
+                problem[0]
+            ))),
+            count => Err(anyhow::Error::msg(format!(
+                "Validation: {count} problems found, nothing was run.\n- {}",
+                problem.join("\n- ")
+            ))),
+        }
+    }
+
+    /// record the artifacts of a step, replacing any earlier artifact at the same path.
+    fn artifact_add(list: &mut Vec<Artifact>, artifact: Vec<Artifact>) {
+        list.retain(|x| !artifact.iter().any(|y| y.path == x.path));
+        list.extend(artifact);
+    }
+
+    fn job_default(job: &usize) -> bool {
+        *job == 0
+    }
+
+    /// get the active profile, if any, checking that everything it overrides exists.
+    pub fn profile_get(&self) -> anyhow::Result<Option<&Profile>> {
+        if self.profile_active.is_empty() {
+            return Ok(None);
+        }
+
+        let name = &self.profile_active;
+        let profile = self.profile.get(name).ok_or(anyhow::Error::msg(format!(
+            "Profile: No profile named \"{name}\"."
+        )))?;
+
+        for key in profile.meta.keys() {
+            if !Meta::LIST_FIELD.contains(&key.as_str()) {
+                return Err(anyhow::Error::msg(format!(
+                    "Profile \"{name}\": No project field named \"{key}\"."
+                )));
+            }
+        }
+
+        for key in profile.enable.keys() {
+            if !self
+                .compile
+                .iter()
+                .chain(self.package.iter())
+                .any(|step| step.get_name() == key)
+            {
+                return Err(anyhow::Error::msg(format!(
+                    "Profile \"{name}\": No compile or package step named \"{key}\"."
+                )));
+            }
+        }
+
+        Ok(Some(profile))
+    }
+
+    /// get a copy of the project meta-data with every placeholder replaced, dated to the time of a
+    /// reproducible build if there is one, and with a template for the exporters to fill in their
+    /// own fields with.
+    pub fn meta_resolve(&self) -> anyhow::Result<Meta> {
+        let profile = self.profile_get()?;
+        let mut meta = self.meta.clone();
+        let mut template = Template::new(&meta.path);
+        let mut variable = self.variable.clone();
+
+        // date the run to the time of a reproducible build, if there is one.
+        meta.epoch = meta.epoch()?;
+
+        if let Some(epoch) = meta.epoch {
+            template.set_date(epoch);
+        }
+
+        // apply the active profile's overrides.
+        if let Some(profile) = profile {
+            for (key, field) in Meta::LIST_FIELD.iter().zip(meta.field_mut()) {
+                if let Some(value) = profile.meta.get(*key) {
+                    *field = value.clone();
+                }
+            }
+
+            variable.extend(profile.variable.clone());
+        }
+
+        // variables can only refer to built-in placeholders.
+        let mut list = Vec::new();
+
+        for (key, value) in &variable {
+            if Template::LIST_BUILT_IN.contains(&key.as_str())
+                || Meta::LIST_FIELD.contains(&key.as_str())
+                || key.contains('.')
+            {
+                return Err(anyhow::Error::msg(format!(
+                    "Template: Variable \"{key}\" would shadow a built-in placeholder."
+                )));
+            }
+
+            list.push((key, template.format(value)?));
+        }
+
+        for (key, value) in list {
+            template.insert(key, &value);
+        }
+
+        // fill in empty project fields from the Cargo manifest.
+        if !meta.cargo.is_empty() {
+            meta.cargo = template.format(&meta.cargo)?;
+            template.set_manifest(meta.resolve(&meta.cargo));
+
+            for (key, field) in Meta::LIST_FIELD.iter().zip(meta.field_mut()) {
+                if field.is_empty()
+                    && let Some((_, name)) = Meta::LIST_CARGO.iter().find(|(x, _)| x == key)
+                    && let Some(value) = template.cargo_key(name)?
+                {
+                    *field = value;
+                }
+            }
+        }
+
+        // project fields can refer to variables, too.
+        for field in meta.field_mut() {
+            *field = template.format(field)?;
+        }
+
+        for (key, value) in Meta::LIST_FIELD.iter().zip(meta.field_mut()) {
+            template.insert(key, value);
+        }
+
+        meta.output = meta.resolve(&template.format(&self.output)?);
+        meta.output_name = self.output_name.clone();
+        meta.signing = Signing {
+            key: template.format(&self.signing.key)?,
+            key_file: template.format(&self.signing.key_file)?,
+            ..self.signing.clone()
+        };
+
+        if !meta.signing.key_file.is_empty() {
+            meta.signing.key_file = meta.resolve(&meta.signing.key_file).display().to_string();
+        }
+
+        meta.template = template;
+
+        Ok(meta)
+    }
+
+    /// load a project, from either the project folder or the project file itself.
+    pub fn load(path: PathBuf) -> anyhow::Result<Self> {
+        let (path, file) = Self::locate(path)?;
+
+        let mut value = Format::from_path(&file)?.read(&std::fs::read_to_string(&file)?)?;
+        migration::migrate(&mut value)?;
+
+        let mut result: Self = serde_json::from_value(value)?;
+        result.meta.path = path;
+        result.file = file;
+
+        Scheduler::graph(&result.compile)?;
+        Scheduler::graph(&result.package)?;
+
+        Ok(result)
+    }
+
+    /// upgrade a project file in place to the current format version, keeping a backup of the original.
+    /// returns false if the project file was already up-to-date.
+    pub fn migrate(path: PathBuf) -> anyhow::Result<bool> {
+        let (_, file) = Self::locate(path.clone())?;
+
+        let value = Format::from_path(&file)?.read(&std::fs::read_to_string(&file)?)?;
+
+        if migration::version(&value)? == FORMAT_VERSION {
+            return Ok(false);
+        }
+
+        let mut result = Self::load(path)?;
+
+        std::fs::copy(&file, Self::backup(&file))?;
+
+        result.save()?;
+
+        Ok(true)
+    }
+
+    /// rewrite a project file in another format. the original is kept as a backup, out of the way of look-up.
+    pub fn convert(path: PathBuf, format: Format) -> anyhow::Result<PathBuf> {
+        let mut result = Self::load(path)?;
+        let source = result.file.clone();
+
+        if Format::from_path(&source)? == format {
+            return Ok(source);
+        }
+
+        result.file = result.meta.path.join(format.file_name());
+
+        if result.file.exists() {
+            return Err(anyhow::Error::msg(format!(
+                "Convert: \"{}\" already exists.",
+                result.file.display()
+            )));
+        }
+
+        result.save()?;
+
+        std::fs::rename(&source, Self::backup(&source))?;
+
+        Ok(result.file)
+    }
+
+    /// get the project folder and project file from either one of them.
+    pub fn locate(path: PathBuf) -> anyhow::Result<(PathBuf, PathBuf)> {
+        let path = std::fs::canonicalize(path)?;
+
+        if path.is_file() {
+            let folder = path.parent().map(Path::to_path_buf).unwrap_or_default();
+            return Ok((folder, path));
+        }
+
+        for (name, _) in Format::LIST_FILE {
+            let file = path.join(name);
+
+            if file.is_file() {
+                return Ok((path, file));
+            }
+        }
+
+        let file = path.join(Format::default().file_name());
+
+        Ok((path, file))
+    }
+
+    /// get the back-up path of a project file.
+    fn backup(file: &Path) -> PathBuf {
+        let mut backup = file.to_path_buf().into_os_string();
+        backup.push(".bak");
+
+        backup.into()
+    }
+
+    /// save the project into its project file, storing every path relative to the project folder.
+    pub fn save(&mut self) -> anyhow::Result<()> {
+        self.meta.icon = self.meta.relative(&self.meta.icon);
+        self.meta.cargo = self.meta.relative(&self.meta.cargo);
+        self.stage = self.meta.relative(&self.stage);
+        self.output = self.meta.relative(&self.output);
+        self.signing.key_file = self.meta.relative(&self.signing.key_file);
+
+        for export in self.compile.iter_mut().chain(self.package.iter_mut()) {
+            for path in export.get_path() {
+                *path = self.meta.relative(path);
+            }
+        }
+
+        let data = Format::from_path(&self.file)?.write(self)?;
+
+        Ok(std::fs::write(&self.file, data)?)
+    }
+}
+
+//================================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exporter::{debian::Debian, script::Script};
+
+    /// get a step that runs nothing, by name.
+    fn step(name: &str) -> Box<dyn Export> {
+        serde_json::from_value(serde_json::json!({
+            "type": "Script",
+            "name": name,
+            "script": "",
+            "layout": [],
+            "enable": true,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn profile() {
+        let mut project = Project::default();
+        project.meta.name = "app".to_string();
+        project.meta.info = "An app.".to_string();
+        project.meta.version = "1.0-{channel}".to_string();
+        project
+            .variable
+            .insert("channel".to_string(), "stable".to_string());
+        project.package.push(step("deb"));
+
+        let mut nightly = Profile::default();
+        nightly
+            .meta
+            .insert("info".to_string(), "A {channel} build.".to_string());
+        nightly
+            .variable
+            .insert("channel".to_string(), "nightly".to_string());
+        nightly.enable.insert("deb".to_string(), false);
+        project.profile.insert("nightly".to_string(), nightly);
+
+        let meta = project.meta_resolve().unwrap();
+        assert_eq!(
+            (meta.version.as_str(), meta.info.as_str()),
+            ("1.0-stable", "An app.")
+        );
+        assert!(Profile::step_enable(None, project.package[0].as_ref()));
+
+        // the profile's fields, variables and step switches win over the project's own.
+        project.profile_active = "nightly".to_string();
+
+        let meta = project.meta_resolve().unwrap();
+        assert_eq!(meta.version, "1.0-nightly");
+        assert_eq!(meta.info, "A nightly build.");
+        assert!(!Profile::step_enable(
+            project.profile_get().unwrap(),
+            project.package[0].as_ref()
+        ));
+
+        // a profile naming an unknown field or step is an error, as is an unknown profile.
+        let profile = project.profile.get_mut("nightly").unwrap();
+        profile.enable.insert("rpm".to_string(), true);
+        assert!(project.profile_get().is_err());
+
+        let profile = project.profile.get_mut("nightly").unwrap();
+        profile.enable.remove("rpm");
+        profile.meta.insert("colour".to_string(), "red".to_string());
+        assert!(project.meta_resolve().is_err());
+
+        project.profile_active = "beta".to_string();
+        assert!(project.profile_get().is_err());
+    }
+
+    #[test]
+    fn validate() {
+        let path = std::env::temp_dir().join(format!("boondle_validate_{}", std::process::id()));
+        std::fs::create_dir_all(&path).unwrap();
+
+        let mut project = Project::create(&path).unwrap();
+        project.meta.name = "app".to_string();
+        project.meta.version = "1.0".to_string();
+
+        for (name, enable) in [("a", true), ("b", true), ("c", false)] {
+            let mut step = step(name);
+            let script = step.as_any_mut().downcast_mut::<Script>().unwrap();
+            script.script = format!("{name}.sh");
+            script.enable = enable;
+            project.compile.push(step);
+        }
+
+        // every problem of every enabled step is reported at once, and nothing is run.
+        let error = project.compile().unwrap_err().to_string();
+        assert!(
+            error.starts_with("Validation: 2 problems found, nothing was run."),
+            "{error}"
+        );
+        assert!(
+            error.contains("\"a.sh\" does not exist") && error.contains("\"b.sh\" does not exist")
+        );
+
+        // each problem is prefixed with the step it was found in.
+        assert!(
+            error.contains("\n- [a] ") && error.contains("\n- [b] "),
+            "{error}"
+        );
+        assert!(!error.contains("c.sh"));
+        assert!(project.schedule_compile.is_none());
+
+        std::fs::write(path.join("a.sh"), "").unwrap();
+
+        let error = project.compile().unwrap_err().to_string();
+        assert!(
+            error.starts_with("Validation: 1 problem found, nothing was run."),
+            "{error}"
+        );
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+
+    #[test]
+    fn output_check() {
+        let mut project = Project::default();
+        project.meta.name = "app".to_string();
+        project.meta.version = "1.0".to_string();
+        project.output = "dist".to_string();
+
+        for (name, architecture) in [("deb", "amd64"), ("deb_arm", "arm64"), ("deb_old", "amd64")] {
+            let mut step = Debian::default();
+            step.name = name.to_string();
+            step.file = "{name}_{version}_{arch}".to_string();
+            step.architecture = architecture.to_string();
+            step.enable = true;
+            project.package.push(Box::new(step));
+        }
+
+        let meta = project.meta_resolve().unwrap();
+        assert_eq!(meta.output, PathBuf::from("dist"));
+
+        // the two amd64 steps would write the same file, the arm64 one would not.
+        assert_eq!(
+            project.output_check(&meta, None),
+            vec![
+                "Output: Steps \"deb\" and \"deb_old\" would both write \"dist/app_1.0_amd64.deb\"."
+                    .to_string()
+            ]
+        );
+
+        // a step turned off by the profile writes nothing.
+        let mut profile = Profile::default();
+        profile.enable.insert("deb_old".to_string(), false);
+        assert!(project.output_check(&meta, Some(&profile)).is_empty());
+
+        // with the default file name, named after the step, the steps don't collide either.
+        project.package[2]
+            .as_any_mut()
+            .downcast_mut::<Debian>()
+            .unwrap()
+            .file
+            .clear();
+        assert!(project.output_check(&meta, None).is_empty());
+    }
+
+    #[test]
+    fn epoch() {
+        let path = std::env::temp_dir().join(format!("boondle_epoch_{}", std::process::id()));
+        std::fs::create_dir_all(&path).unwrap();
+
+        let meta = Meta {
+            path: path.clone(),
+            ..Default::default()
+        };
+
+        assert_eq!(Meta::epoch_parse(None).unwrap(), None);
+        assert_eq!(
+            Meta::epoch_parse(Some("1700000000\n")).unwrap(),
+            Some(1_700_000_000)
+        );
+        assert!(Meta::epoch_parse(Some("yesterday")).is_err());
+
+        // without SOURCE_DATE_EPOCH, the time of the last git commit is used, if there is one.
+        if Command::new("git").arg("--version").output().is_ok() {
+            assert_eq!(meta.epoch_git(), None);
+
+            let git = |argument: &[&str]| {
+                let status = Command::new("git")
+                    .arg("-C")
+                    .arg(&path)
+                    .args(["-c", "user.name=Boondle", "-c", "user.email=test@boondle"])
+                    .args(argument)
+                    .env("GIT_COMMITTER_DATE", "@1600000000 +0000")
+                    .output()
+                    .unwrap()
+                    .status;
+                assert!(status.success());
+            };
+
+            git(&["init", "-q"]);
+            git(&["commit", "-q", "--allow-empty", "-m", "Initial commit."]);
+
+            assert_eq!(meta.epoch_git(), Some(1_600_000_000));
+        }
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+
+    #[test]
+    fn relative() {
+        let meta = Meta {
+            path: PathBuf::from("/home/user/app"),
+            ..Default::default()
+        };
+
+        assert_eq!(meta.relative("/home/user/app/icon.png"), "icon.png");
+        assert_eq!(
+            meta.relative("/home/user/app/asset/icon.png"),
+            "asset/icon.png"
+        );
+        assert_eq!(
+            meta.relative("/home/user/other/icon.png"),
+            "../other/icon.png"
+        );
+        assert_eq!(meta.relative("/home/icon.png"), "../../icon.png");
+        // a path that only shares the root folder stays absolute.
+        assert_eq!(meta.relative("/usr/share/icon.png"), "/usr/share/icon.png");
+        assert_eq!(meta.relative("asset/icon.png"), "asset/icon.png");
+
+        // a relative path resolves back to the absolute one.
+        assert_eq!(
+            meta.resolve(&meta.relative("/home/user/other/icon.png")),
+            PathBuf::from("/home/user/app/../other/icon.png")
+        );
+    }
+
+    #[test]
+    fn binary_from() {
+        let path = std::env::temp_dir().join(format!("boondle_binary_from_{}", std::process::id()));
+        std::fs::create_dir_all(&path).unwrap();
+
+        let mut project = Project::create(&path).unwrap();
+        project.meta.name = "app".to_string();
+        project.compile.push(
+            serde_json::from_value(serde_json::json!({
+                "type": "Script",
+                "name": "build",
+                "script": "",
+                "layout": [],
+                "output": [
+                    { "path": "build/{name}_arm", "kind": "Binary", "architecture": "arm64" },
+                    { "path": "build/{name}", "kind": "Binary" },
+                ],
+                "enable": true,
+            }))
+            .unwrap(),
+        );
+
+        let mut step = Debian::default();
+        step.name = "deb".to_string();
+        step.binary_from = "build".to_string();
+        step.architecture = "arm64".to_string();
+        step.enable = true;
+        project.package.push(Box::new(step));
+
+        let mut meta = project.meta_resolve().unwrap();
+        meta.artifact = project.artifact_declare(&meta).unwrap();
+
+        // each binary is found by its own architecture, or the host's if it sets none.
+        assert_eq!(
+            meta.binary("build", "", "arm64").unwrap(),
+            Some(path.join("build/app_arm"))
+        );
+        assert_eq!(
+            meta.binary("build", "", std::env::consts::ARCH).unwrap(),
+            Some(path.join("build/app"))
+        );
+
+        // the package step needs the step's binaries to be built first.
+        assert_eq!(project.artifact_check(&meta, None).len(), 2);
+
+        std::fs::create_dir_all(path.join("build")).unwrap();
+        std::fs::write(path.join("build/app_arm"), "").unwrap();
+        std::fs::write(path.join("build/app"), "").unwrap();
+        assert!(project.artifact_check(&meta, None).is_empty());
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+
+    #[test]
+    fn clean() {
+        let path = std::env::temp_dir().join(format!("boondle_clean_{}", std::process::id()));
+        let stage = path.join("stage");
+        std::fs::create_dir_all(path.join("project")).unwrap();
+
+        for folder in [
+            "2024-01-31_120000/usr",
+            "2024-01-31_120000_1",
+            "reproducible/0",
+            "other",
+        ] {
+            std::fs::create_dir_all(stage.join(folder)).unwrap();
+        }
+        std::fs::write(stage.join("notes.txt"), "keep").unwrap();
+
+        let mut project = Project::create(&path.join("project")).unwrap();
+        project.stage = stage.display().to_string();
+
+        let mut list = project.clean(false).unwrap();
+        list.sort();
+        assert_eq!(
+            list,
+            vec![
+                stage.join("2024-01-31_120000"),
+                stage.join("2024-01-31_120000_1"),
+                stage.join("reproducible"),
+            ]
+        );
+
+        // anything Boondle did not make is left alone.
+        assert!(stage.join("notes.txt").is_file());
+        assert!(stage.join("other").is_dir());
+
+        // with the packages, every signature of them goes too, but a compile step's output stays.
+        project.compile.push(
+            serde_json::from_value(serde_json::json!({
+                "type": "Script",
+                "name": "archive",
+                "script": "",
+                "layout": [],
+                "output": [{ "path": "app.tar.gz" }],
+                "enable": true,
+            }))
+            .unwrap(),
+        );
+
+        let mut step = Debian::default();
+        step.name = "deb".to_string();
+        step.file = "app".to_string();
+        step.enable = true;
+        project.package.push(Box::new(step));
+        project.package.push(
+            serde_json::from_value(serde_json::json!({
+                "type": "Sign",
+                "name": "sign",
+                "enable": true,
+            }))
+            .unwrap(),
+        );
+        project.signing.key = "Boondle".to_string();
+
+        for file in ["app.deb", "app.deb.asc", "app.tar.gz", "app.tar.gz.asc"] {
+            std::fs::write(path.join("project").join(file), "").unwrap();
+        }
+
+        let mut list = project.clean(true).unwrap();
+        list.sort();
+        assert_eq!(
+            list,
+            vec![
+                path.join("project/app.deb"),
+                path.join("project/app.deb.asc"),
+                path.join("project/app.tar.gz.asc"),
+            ]
+        );
+        assert!(path.join("project/app.tar.gz").is_file());
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+}
@@ -0,0 +1,365 @@
+/*
+* Copyright (c) 2025 luxreduxdelux
+*
+* Redistribution and use in source and binary forms, with or without
+* modification, are permitted provided that the following conditions are met:
+*
+* 1. Redistributions of source code must retain the above copyright notice,
+* this list of conditions and the following disclaimer.
+*
+* 2. Redistributions in binary form must reproduce the above copyright notice,
+* this list of conditions and the following disclaimer in the documentation
+* and/or other materials provided with the distribution.
+*
+* Subject to the terms and conditions of this license, each copyright holder
+* and contributor hereby grants to those receiving rights under this license
+* a perpetual, worldwide, non-exclusive, no-charge, royalty-free, irrevocable
+* (except for failure to satisfy the conditions of this license) patent license
+* to make, have made, use, offer to sell, sell, import, and otherwise transfer
+* this software, where such license applies only to those patent claims, already
+* acquired or hereafter acquired, licensable by such copyright holder or
+* contributor that are necessarily infringed by:
+*
+* (a) their Contribution(s) (the licensed copyrights of copyright holders and
+* non-copyrightable additions of contributors, in source or binary form) alone;
+* or
+*
+* (b) combination of their Contribution(s) with the work of authorship to which
+* such Contribution(s) was added by such copyright holder or contributor, if,
+* at the time the Contribution is added, such addition causes such combination
+* to be necessarily infringed. The patent license shall not apply to any other
+* combinations which include the Contribution.
+*
+* Except as expressly stated above, no rights or licenses from any copyright
+* holder or contributor is granted under this license, whether expressly, by
+* implication, estoppel or otherwise.
+*
+* DISCLAIMER
+*
+* THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+* AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+* IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+* DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS OR CONTRIBUTORS BE LIABLE
+* FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+* DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+* SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+* CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+* OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+* OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+*/
+
+use crate::{
+    exporter::{
+        artifact::Artifact,
+        export::{Export, ExportStatus},
+    },
+    project::{Meta, Profile},
+};
+
+//================================================================
+
+use std::time::Instant;
+
+//================================================================
+
+/// state of a step in a schedule.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum State {
+    Wait,
+    Run,
+    /// finished, with whether the step's dependents may run.
+    Done(bool),
+}
+
+/// mark of a step in the search for a cycle.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mark {
+    New,
+    Visit,
+    Done,
+}
+
+/// runs a list of steps in dependency order, with independent steps in parallel.
+pub struct Scheduler {
+    pub meta: Meta,
+    pub start: Instant,
+    job: usize,
+    need: Vec<Vec<usize>>,
+    state: Vec<State>,
+    /// artifacts produced by every step that has succeeded.
+    pub artifact: Vec<Artifact>,
+}
+
+impl Scheduler {
+    /// create a schedule for every enabled step, running at most `job` steps at once (0 for one per CPU core).
+    pub fn new(
+        list: &mut [Box<dyn Export>],
+        meta: Meta,
+        job: usize,
+        profile: Option<&Profile>,
+    ) -> anyhow::Result<Self> {
+        let need = Self::graph(list)?;
+
+        // a step that is turned off doesn't hold back the steps that only come after it by rank,
+        // but every step that needs it by name is skipped, along with every step that needs those.
+        let off: Vec<String> = list
+            .iter()
+            .filter(|step| !Profile::step_enable(profile, step.as_ref()))
+            .map(|step| step.get_name().to_string())
+            .collect();
+
+        let state = list
+            .iter_mut()
+            .map(|step| {
+                if !Profile::step_enable(profile, step.as_ref()) {
+                    step.set_status(ExportStatus::Skipped);
+                    State::Done(true)
+                } else if step.get_need().iter().any(|name| off.contains(name)) {
+                    step.clear();
+                    step.set_status(ExportStatus::Skipped);
+                    State::Done(false)
+                } else {
+                    step.clear();
+                    step.set_status(ExportStatus::Waiting);
+                    State::Wait
+                }
+            })
+            .collect();
+
+        let job = if job == 0 {
+            std::thread::available_parallelism().map_or(1, usize::from)
+        } else {
+            job
+        };
+
+        Ok(Self {
+            meta,
+            start: Instant::now(),
+            job,
+            need,
+            state,
+            artifact: Vec::default(),
+        })
+    }
+
+    /// get the prerequisites of every step, by index. a step needs every step of a lower rank, too.
+    /// fails on a step that needs an unknown step, or on a cycle.
+    pub fn graph(list: &[Box<dyn Export>]) -> anyhow::Result<Vec<Vec<usize>>> {
+        let mut result = Vec::with_capacity(list.len());
+
+        for step in list {
+            let mut need = Vec::new();
+
+            for name in step.get_need() {
+                let index: Vec<usize> = list
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, x)| x.get_name() == name)
+                    .map(|(i, _)| i)
+                    .collect();
+
+                if index.is_empty() {
+                    return Err(anyhow::Error::msg(format!(
+                        "Schedule: Step \"{}\" needs \"{name}\", but no step has that name.",
+                        step.get_name()
+                    )));
+                }
+
+                need.extend(index);
+            }
+
+            need.extend(
+                list.iter()
+                    .enumerate()
+                    .filter(|(_, x)| x.get_rank() < step.get_rank())
+                    .map(|(i, _)| i),
+            );
+
+            result.push(need);
+        }
+
+        let mut mark = vec![Mark::New; list.len()];
+
+        for index in 0..list.len() {
+            if let Some(cycle) = Self::cycle(index, &result, &mut mark, &mut Vec::new()) {
+                let cycle: Vec<String> = cycle
+                    .iter()
+                    .map(|x| format!("\"{}\"", list[*x].get_name()))
+                    .collect();
+
+                return Err(anyhow::Error::msg(format!(
+                    "Schedule: Steps need each other in a cycle: {}.",
+                    cycle.join(" needs ")
+                )));
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// depth-first search for a cycle from a step, returning the steps in the cycle.
+    fn cycle(
+        index: usize,
+        need: &[Vec<usize>],
+        mark: &mut [Mark],
+        path: &mut Vec<usize>,
+    ) -> Option<Vec<usize>> {
+        match mark[index] {
+            Mark::Done => return None,
+            Mark::Visit => {
+                let start = path.iter().position(|x| *x == index).unwrap_or_default();
+                let mut cycle = path[start..].to_vec();
+                cycle.push(index);
+
+                return Some(cycle);
+            }
+            Mark::New => {}
+        }
+
+        mark[index] = Mark::Visit;
+        path.push(index);
+
+        for next in &need[index] {
+            if let Some(cycle) = Self::cycle(*next, need, mark, path) {
+                return Some(cycle);
+            }
+        }
+
+        path.pop();
+        mark[index] = Mark::Done;
+
+        None
+    }
+
+    /// cancel every step still waiting or running.
+    pub fn cancel(&mut self, list: &mut [Box<dyn Export>]) {
+        for (index, step) in list.iter_mut().enumerate() {
+            match self.state[index] {
+                State::Wait => {
+                    step.set_status(ExportStatus::Cancelled);
+                    self.state[index] = State::Done(false);
+                }
+                State::Run => step.stop(ExportStatus::Cancelled),
+                State::Done(_) => {}
+            }
+        }
+    }
+
+    /// poll the running steps, then start every step whose prerequisites have succeeded, up to the job limit.
+    /// a step whose prerequisite has failed is skipped. returns true once every step is done.
+    pub fn poll(&mut self, list: &mut [Box<dyn Export>]) -> bool {
+        for (index, step) in list.iter_mut().enumerate() {
+            if self.state[index] == State::Run {
+                step.poll_completion();
+
+                if step.success_or_failure() {
+                    let success = *step.get_status() == ExportStatus::Success;
+
+                    // later steps see what earlier ones produced, e.g. to checksum every package.
+                    if success {
+                        let produce = step.get_produce();
+                        self.artifact.extend(produce.iter().cloned());
+                        self.meta.artifact.extend(produce.iter().cloned());
+                    }
+
+                    self.state[index] = State::Done(success);
+                }
+            }
+        }
+
+        let mut run = self.state.iter().filter(|x| **x == State::Run).count();
+
+        for (index, step) in list.iter_mut().enumerate() {
+            if self.state[index] != State::Wait {
+                continue;
+            }
+
+            let need: Vec<State> = self.need[index].iter().map(|x| self.state[*x]).collect();
+
+            if need.contains(&State::Done(false)) {
+                step.set_status(ExportStatus::Skipped);
+                self.state[index] = State::Done(false);
+            } else if run < self.job && need.iter().all(|x| *x == State::Done(true)) {
+                match step.run(self.meta.clone()) {
+                    Ok(_) => {
+                        self.state[index] = State::Run;
+                        run += 1;
+                    }
+                    Err(error) => {
+                        step.set_status(ExportStatus::Failure(error.to_string()));
+                        self.state[index] = State::Done(false);
+                    }
+                }
+            }
+        }
+
+        self.state.iter().all(|x| matches!(x, State::Done(_)))
+    }
+}
+
+//================================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    fn step(name: &str, need: &[&str]) -> Box<dyn Export> {
+        serde_json::from_value(json!({
+            "type": "Script",
+            "name": name,
+            "script": "",
+            "layout": [],
+            "need": need,
+            "enable": true,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn disable() {
+        let mut list = vec![
+            step("deb", &[]),
+            step("apt", &["deb"]),
+            step("mirror", &["apt"]),
+            step("tar", &[]),
+        ];
+
+        let mut profile = Profile::default();
+        profile.enable.insert("deb".to_string(), false);
+
+        let mut scheduler = Scheduler::new(&mut list, Meta::default(), 1, Some(&profile)).unwrap();
+
+        while !scheduler.poll(&mut list) {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        let status: Vec<String> = list
+            .iter_mut()
+            .map(|x| x.get_status().to_string())
+            .collect();
+        assert_eq!(status, ["Skipped", "Skipped", "Skipped", "Success"]);
+    }
+
+    #[test]
+    fn graph() {
+        let list = vec![step("a", &[]), step("b", &["a"]), step("c", &["a", "b"])];
+        assert_eq!(
+            Scheduler::graph(&list).unwrap(),
+            vec![vec![], vec![0], vec![0, 1]]
+        );
+
+        let list = vec![step("a", &["c"]), step("b", &["a"]), step("c", &["b"])];
+        let error = Scheduler::graph(&list).unwrap_err().to_string();
+        assert!(
+            error.contains("\"a\" needs \"c\" needs \"b\" needs \"a\""),
+            "{error}"
+        );
+
+        let list = vec![step("a", &["a"])];
+        assert!(Scheduler::graph(&list).is_err());
+
+        let list = vec![step("a", &["z"])];
+        assert!(Scheduler::graph(&list).is_err());
+    }
+}
@@ -0,0 +1,169 @@
+/*
+* Copyright (c) 2025 luxreduxdelux
+*
+* Redistribution and use in source and binary forms, with or without
+* modification, are permitted provided that the following conditions are met:
+*
+* 1. Redistributions of source code must retain the above copyright notice,
+* this list of conditions and the following disclaimer.
+*
+* 2. Redistributions in binary form must reproduce the above copyright notice,
+* this list of conditions and the following disclaimer in the documentation
+* and/or other materials provided with the distribution.
+*
+* Subject to the terms and conditions of this license, each copyright holder
+* and contributor hereby grants to those receiving rights under this license
+* a perpetual, worldwide, non-exclusive, no-charge, royalty-free, irrevocable
+* (except for failure to satisfy the conditions of this license) patent license
+* to make, have made, use, offer to sell, sell, import, and otherwise transfer
+* this software, where such license applies only to those patent claims, already
+* acquired or hereafter acquired, licensable by such copyright holder or
+* contributor that are necessarily infringed by:
+*
+* (a) their Contribution(s) (the licensed copyrights of copyright holders and
+* non-copyrightable additions of contributors, in source or binary form) alone;
+* or
+*
+* (b) combination of their Contribution(s) with the work of authorship to which
+* such Contribution(s) was added by such copyright holder or contributor, if,
+* at the time the Contribution is added, such addition causes such combination
+* to be necessarily infringed. The patent license shall not apply to any other
+* combinations which include the Contribution.
+*
+* Except as expressly stated above, no rights or licenses from any copyright
+* holder or contributor is granted under this license, whether expressly, by
+* implication, estoppel or otherwise.
+*
+* DISCLAIMER
+*
+* THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+* AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+* IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+* DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS OR CONTRIBUTORS BE LIABLE
+* FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+* DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+* SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+* CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+* OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+* OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+*/
+
+use crate::{
+    exporter::{
+        app_image::AppImage, cargo::Cargo, checksum::Checksum, debian::Debian, script::Script,
+        sign::Sign,
+    },
+    project::Project,
+};
+
+//================================================================
+
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
+use serde_json::Value;
+use std::borrow::Cow;
+
+//================================================================
+
+/// stand-in for `Box<dyn Export>`: a union of every exporter, told apart by the `type` tag.
+pub struct ExportSchema;
+
+impl ExportSchema {
+    /// get the schema of an exporter, with the `type` tag typetag writes in front of it.
+    fn tagged<T: JsonSchema>(generator: &mut SchemaGenerator, name: &str) -> Schema {
+        let mut schema = T::json_schema(generator);
+        let object = schema.ensure_object();
+
+        object.insert("title".to_string(), Value::from(name));
+
+        if let Some(Value::Object(property)) = object.get_mut("properties") {
+            property.insert(
+                "type".to_string(),
+                json_schema!({ "const": name }).to_value(),
+            );
+        }
+
+        if let Some(Value::Array(required)) = object.get_mut("required") {
+            required.insert(0, Value::from("type"));
+        }
+
+        schema
+    }
+}
+
+impl JsonSchema for ExportSchema {
+    fn schema_name() -> Cow<'static, str> {
+        "Export".into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "oneOf": [
+                Self::tagged::<AppImage>(generator, "AppImage"),
+                Self::tagged::<Cargo>(generator, "Cargo"),
+                Self::tagged::<Checksum>(generator, "Checksum"),
+                Self::tagged::<Debian>(generator, "Debian"),
+                Self::tagged::<Script>(generator, "Script"),
+                Self::tagged::<Sign>(generator, "Sign"),
+            ]
+        })
+    }
+}
+
+/// get the JSON Schema of the project file.
+pub fn schema() -> anyhow::Result<String> {
+    Ok(serde_json::to_string_pretty(&schemars::schema_for!(
+        Project
+    ))?)
+}
+
+//================================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const FIXTURE: [(&str, &str); 2] = [
+        (
+            "boondle/project.json",
+            include_str!("../../boondle/project.json"),
+        ),
+        (
+            "tests/fixture/project_v1.json",
+            include_str!("../tests/fixture/project_v1.json"),
+        ),
+    ];
+
+    #[test]
+    fn schema_valid() {
+        let schema: Value = serde_json::from_str(&schema().unwrap()).unwrap();
+        let validator = jsonschema::validator_for(&schema).unwrap();
+
+        for (name, text) in FIXTURE {
+            let mut value: Value = serde_json::from_str(text).unwrap();
+            value["$schema"] = Value::from("project.schema.json");
+
+            let error: Vec<String> = validator
+                .iter_errors(&value)
+                .map(|x| format!("{}: {x}", x.instance_path))
+                .collect();
+            assert!(error.is_empty(), "{name}: {error:?}");
+            assert!(
+                serde_json::from_value::<Project>(value.clone()).is_ok(),
+                "{name}"
+            );
+
+            // a field the project doesn't know is rejected by both the schema and the loader.
+            value["meta"]["unknown"] = Value::from(true);
+            assert!(!validator.is_valid(&value), "{name}");
+            assert!(
+                serde_json::from_value::<Project>(value.clone()).is_err(),
+                "{name}"
+            );
+
+            value["meta"].as_object_mut().unwrap().remove("unknown");
+            value["package"][0]["unknown"] = Value::from(true);
+            assert!(!validator.is_valid(&value), "{name}");
+            assert!(serde_json::from_value::<Project>(value).is_err(), "{name}");
+        }
+    }
+}
@@ -0,0 +1,446 @@
+/*
+* Copyright (c) 2025 luxreduxdelux
+*
+* Redistribution and use in source and binary forms, with or without
+* modification, are permitted provided that the following conditions are met:
+*
+* 1. Redistributions of source code must retain the above copyright notice,
+* this list of conditions and the following disclaimer.
+*
+* 2. Redistributions in binary form must reproduce the above copyright notice,
+* this list of conditions and the following disclaimer in the documentation
+* and/or other materials provided with the distribution.
+*
+* Subject to the terms and conditions of this license, each copyright holder
+* and contributor hereby grants to those receiving rights under this license
+* a perpetual, worldwide, non-exclusive, no-charge, royalty-free, irrevocable
+* (except for failure to satisfy the conditions of this license) patent license
+* to make, have made, use, offer to sell, sell, import, and otherwise transfer
+* this software, where such license applies only to those patent claims, already
+* acquired or hereafter acquired, licensable by such copyright holder or
+* contributor that are necessarily infringed by:
+*
+* (a) their Contribution(s) (the licensed copyrights of copyright holders and
+* non-copyrightable additions of contributors, in source or binary form) alone;
+* or
+*
+* (b) combination of their Contribution(s) with the work of authorship to which
+* such Contribution(s) was added by such copyright holder or contributor, if,
+* at the time the Contribution is added, such addition causes such combination
+* to be necessarily infringed. The patent license shall not apply to any other
+* combinations which include the Contribution.
+*
+* Except as expressly stated above, no rights or licenses from any copyright
+* holder or contributor is granted under this license, whether expressly, by
+* implication, estoppel or otherwise.
+*
+* DISCLAIMER
+*
+* THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+* AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+* IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+* DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS OR CONTRIBUTORS BE LIABLE
+* FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+* DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+* SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+* CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+* OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+* OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+*/
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::OnceLock,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+//================================================================
+
+type Lookup = BTreeMap<String, Result<String, String>>;
+
+/// placeholder look-up for every string field in the project.
+///
+/// `{key}` is replaced with the value of a variable, `{env.KEY}` with an environment variable,
+/// `{git.KEY}` with the state of the project's repository, `{cargo.KEY}` with a key from the
+/// `[package]` table of the Cargo manifest and `{file.PATH}` with the content of a file.
+/// `{{` and `}}` write a literal brace.
+#[derive(Default, Clone)]
+pub struct Template {
+    variable: BTreeMap<String, String>,
+    path: PathBuf,
+    manifest: Option<PathBuf>,
+    git: OnceLock<Lookup>,
+    cargo: OnceLock<Result<Lookup, String>>,
+}
+
+impl Template {
+    /// every built-in placeholder that isn't a project field.
+    pub const LIST_BUILT_IN: [&'static str; 5] = ["arch", "date", "step", "time", "timestamp"];
+
+    /// create a new template with the built-in placeholders, for a project at the given path.
+    pub fn new(path: &Path) -> Self {
+        let since = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|x| x.as_secs())
+            .unwrap_or_default();
+
+        let mut result = Self {
+            path: path.to_path_buf(),
+            ..Default::default()
+        };
+        result.insert("arch", std::env::consts::ARCH);
+        result.set_date(since);
+
+        result
+    }
+
+    /// set the `{date}`, `{time}` and `{timestamp}` placeholders to a time, in seconds since the
+    /// UNIX epoch, e.g. that of a reproducible build.
+    pub fn set_date(&mut self, since: u64) {
+        let (year, month, day) = Self::date_from_day((since / 86400) as i64);
+        let time = since % 86400;
+
+        self.insert("date", &format!("{year:04}-{month:02}-{day:02}"));
+        self.insert(
+            "time",
+            &format!("{:02}{:02}{:02}", time / 3600, time % 3600 / 60, time % 60),
+        );
+        self.insert("timestamp", &since.to_string());
+    }
+
+    /// set the Cargo manifest to read `{cargo.KEY}` placeholders from.
+    pub fn set_manifest(&mut self, path: PathBuf) {
+        self.manifest = Some(path);
+        self.cargo = OnceLock::new();
+    }
+
+    /// set a placeholder.
+    pub fn insert(&mut self, key: &str, value: &str) {
+        self.variable.insert(key.to_string(), value.to_string());
+    }
+
+    /// get a copy of the template with an extra placeholder, e.g. an exporter's own architecture.
+    pub fn with(&self, key: &str, value: &str) -> Self {
+        let mut result = self.clone();
+        result.insert(key, value);
+
+        result
+    }
+
+    /// get the value of a placeholder.
+    fn get(&self, key: &str) -> anyhow::Result<String> {
+        if let Some(key) = key.strip_prefix("env.") {
+            return std::env::var(key).map_err(|_| {
+                anyhow::Error::msg(format!("environment variable \"{key}\" is not set"))
+            });
+        }
+
+        if let Some(file) = key.strip_prefix("file.") {
+            return std::fs::read_to_string(self.path.join(file))
+                .map(|data| data.trim().to_string())
+                .map_err(|error| {
+                    anyhow::Error::msg(format!("could not read file \"{file}\" ({error})"))
+                });
+        }
+
+        if key.starts_with("git.") {
+            let git = self.git.get_or_init(|| Self::git(&self.path));
+
+            return match git.get(key) {
+                Some(value) => value.clone().map_err(anyhow::Error::msg),
+                None => Err(anyhow::Error::msg(format!(
+                    "unknown placeholder \"{{{key}}}\", expected one of {}",
+                    git.keys().cloned().collect::<Vec<_>>().join(", ")
+                ))),
+            };
+        }
+
+        if let Some(name) = key.strip_prefix("cargo.") {
+            return self.cargo_key(name)?.ok_or(anyhow::Error::msg(format!(
+                "\"{name}\" is not set in the [package] table of the Cargo manifest"
+            )));
+        }
+
+        self.variable
+            .get(key)
+            .cloned()
+            .ok_or(anyhow::Error::msg(format!(
+                "unknown placeholder \"{{{key}}}\""
+            )))
+    }
+
+    /// get a key from the `[package]` table of the Cargo manifest, if it is set.
+    pub fn cargo_key(&self, name: &str) -> anyhow::Result<Option<String>> {
+        let Some(manifest) = &self.manifest else {
+            return Err(anyhow::Error::msg(format!(
+                "\"{{cargo.{name}}}\" needs a Cargo manifest to be set in the project"
+            )));
+        };
+
+        let cargo = self
+            .cargo
+            .get_or_init(|| Self::cargo(manifest))
+            .as_ref()
+            .map_err(|error| anyhow::Error::msg(error.clone()))?;
+
+        match cargo.get(name) {
+            Some(value) => Ok(Some(value.clone().map_err(anyhow::Error::msg)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// replace every placeholder in a string.
+    pub fn format(&self, text: &str) -> anyhow::Result<String> {
+        let mut result = String::with_capacity(text.len());
+        let mut chars = text.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    result.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    result.push('}');
+                }
+                '{' => {
+                    let mut key = String::new();
+
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => key.push(c),
+                            None => {
+                                return Err(anyhow::Error::msg(format!(
+                                    "Template: Unclosed placeholder in \"{text}\"."
+                                )));
+                            }
+                        }
+                    }
+
+                    let value = self.get(key.trim()).map_err(|error| {
+                        anyhow::Error::msg(format!("Template: {error}, in \"{text}\"."))
+                    })?;
+
+                    result.push_str(&value);
+                }
+                '}' => {
+                    return Err(anyhow::Error::msg(format!(
+                        "Template: Unmatched \"}}\" in \"{text}\", use \"}}}}\" for a literal brace."
+                    )));
+                }
+                c => result.push(c),
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn git(path: &Path) -> Lookup {
+        let run = |argument: &[&str]| -> Result<String, String> {
+            let out = Command::new("git")
+                .args(argument)
+                .current_dir(path)
+                .output()
+                .map_err(|error| format!("could not run git ({error})"))?;
+
+            if !out.status.success() {
+                return Err(format!(
+                    "git {} failed ({})",
+                    argument.join(" "),
+                    String::from_utf8_lossy(&out.stderr).trim()
+                ));
+            }
+
+            Ok(String::from_utf8_lossy(&out.stdout).trim().to_string())
+        };
+
+        let describe = run(&["describe", "--tags"]);
+
+        let mut result = BTreeMap::new();
+        result.insert("git.sha".to_string(), run(&["rev-parse", "HEAD"]));
+        result.insert(
+            "git.sha_short".to_string(),
+            run(&["rev-parse", "--short", "HEAD"]),
+        );
+        result.insert(
+            "git.tag".to_string(),
+            run(&["describe", "--tags", "--abbrev=0"]),
+        );
+        result.insert(
+            "git.branch".to_string(),
+            run(&["rev-parse", "--abbrev-ref", "HEAD"]),
+        );
+        result.insert(
+            "git.version".to_string(),
+            describe
+                .clone()
+                .map(|x| x.trim_start_matches('v').to_string()),
+        );
+        result.insert("git.describe".to_string(), describe);
+
+        result
+    }
+
+    /// read every string (or list of strings) in the `[package]` table of a Cargo manifest,
+    /// following `key.workspace = true` to the workspace manifest.
+    fn cargo(path: &Path) -> Result<Lookup, String> {
+        let read = |path: &Path| -> Result<toml::Table, String> {
+            let data = std::fs::read_to_string(path)
+                .map_err(|error| format!("could not read \"{}\" ({error})", path.display()))?;
+
+            toml::from_str(&data)
+                .map_err(|error| format!("could not parse \"{}\" ({error})", path.display()))
+        };
+
+        let manifest = read(path)?;
+        let package = manifest
+            .get("package")
+            .and_then(|x| x.as_table())
+            .ok_or(format!("\"{}\" has no [package] table", path.display()))?;
+
+        // find the nearest workspace manifest, for inherited keys.
+        let workspace = OnceLock::new();
+        let workspace = || {
+            workspace.get_or_init(|| {
+                path.ancestors()
+                    .skip(1)
+                    .map(|x| x.join("Cargo.toml"))
+                    .filter(|x| x.is_file())
+                    .filter_map(|x| read(&x).ok())
+                    .find_map(|x| x.get("workspace")?.get("package")?.as_table().cloned())
+            })
+        };
+
+        let mut result = BTreeMap::new();
+
+        for (key, value) in package {
+            let value = if value.get("workspace").and_then(|x| x.as_bool()) == Some(true) {
+                match workspace().as_ref().and_then(|x| x.get(key)) {
+                    Some(value) => value,
+                    None => {
+                        result.insert(
+                            key.clone(),
+                            Err(format!(
+                                "\"{key}\" is inherited, but no workspace manifest sets it"
+                            )),
+                        );
+                        continue;
+                    }
+                }
+            } else {
+                value
+            };
+
+            let value = match value {
+                toml::Value::String(value) => value.clone(),
+                toml::Value::Array(value) => value
+                    .iter()
+                    .filter_map(|x| x.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                _ => continue,
+            };
+
+            result.insert(key.clone(), Ok(value));
+        }
+
+        Ok(result)
+    }
+
+    /// convert a day count since the UNIX epoch to a (year, month, day) date.
+    fn date_from_day(day: i64) -> (i64, i64, i64) {
+        let day = day + 719468;
+        let era = day.div_euclid(146097);
+        let day_of_era = day.rem_euclid(146097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+        let month = if month_index < 10 {
+            month_index + 3
+        } else {
+            month_index - 9
+        };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        (year, month, day)
+    }
+}
+
+//================================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn template_format() {
+        let mut template = Template::default();
+        template.insert("name", "boondle");
+        template.insert("version", "1.0.0");
+
+        assert_eq!(
+            template.format("{name}_{version}.deb").unwrap(),
+            "boondle_1.0.0.deb"
+        );
+        assert_eq!(template.format("{{name}}").unwrap(), "{name}");
+        assert_eq!(
+            template.with("arch", "amd64").format("{arch}").unwrap(),
+            "amd64"
+        );
+        assert!(template.format("{nmae}").is_err());
+        assert!(template.format("{name").is_err());
+        assert!(template.format("name}").is_err());
+    }
+
+    #[test]
+    fn template_environment() {
+        let template = Template::default();
+
+        assert_eq!(
+            template.format("{env.PATH}").unwrap(),
+            std::env::var("PATH").unwrap()
+        );
+        assert!(template.format("{env.BOONDLE_UNSET_VARIABLE}").is_err());
+    }
+
+    #[test]
+    fn template_cargo() {
+        // the license file is at the root of the workspace.
+        let mut template = Template::new(Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap());
+
+        assert!(template.format("{cargo.version}").is_err());
+
+        template.set_manifest(Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"));
+
+        assert_eq!(
+            template.format("{cargo.name}-{cargo.version}").unwrap(),
+            format!("{}-{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))
+        );
+        assert!(template.format("{cargo.homepage}").is_err());
+        assert_eq!(
+            template.format("{file.LICENSE}").unwrap().lines().next(),
+            Some("Copyright (c) 2025 luxreduxdelux")
+        );
+    }
+
+    #[test]
+    fn template_date() {
+        assert_eq!(Template::date_from_day(0), (1970, 1, 1));
+        assert_eq!(Template::date_from_day(20744), (2026, 10, 18));
+        assert_eq!(Template::date_from_day(11016), (2000, 2, 29));
+
+        let mut template = Template::new(Path::new("/project"));
+        template.set_date(1_700_000_000);
+
+        assert_eq!(
+            template.format("{date} {time} {timestamp}").unwrap(),
+            "2023-11-14 221320 1700000000"
+        );
+    }
+}
@@ -1,5 +0,0 @@
-#!/bin/bash
-
-cd "$(dirname "$0")"
-
-cargo build --release
\ No newline at end of file
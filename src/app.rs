@@ -48,17 +48,18 @@
 * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 */
 
-use crate::{project::*, setting::*};
+use crate::{editor::Editor, setting::*};
 
 //================================================================
 
+use boondle_core::project::Project;
 use eframe::egui;
 
 //================================================================
 
 #[derive(Default)]
 pub struct App {
-    pub project: Option<Project>,
+    pub project: Option<Editor>,
     pub setting: Setting,
 }
 
@@ -99,10 +100,11 @@ impl eframe::App for App {
                     ui.label("New/Load");
 
                     if ui.button("New Project").clicked()
-                        && let Ok(Some(project)) = Self::error(Project::new(), "New Error")
+                        && let Some(path) = rfd::FileDialog::new().pick_folder()
+                        && let Ok(project) = Self::error(Project::create(&path), "New Error")
                     {
                         self.setting.history_add(project.meta.path.clone());
-                        self.project = Some(project);
+                        self.project = Some(Editor::new(project));
                     }
 
                     if ui.button("Load Project").clicked()
@@ -110,7 +112,7 @@ impl eframe::App for App {
                         && let Ok(project) = Self::error(Project::load(path), "Load Error")
                     {
                         self.setting.history_add(project.meta.path.clone());
-                        self.project = Some(project);
+                        self.project = Some(Editor::new(project));
                     };
                 });
 
@@ -124,7 +126,7 @@ impl eframe::App for App {
                             && let Ok(project) =
                                 Self::error(Project::load(path.into()), "Load Error")
                         {
-                            self.project = Some(project);
+                            self.project = Some(Editor::new(project));
                         }
                     }
                 });
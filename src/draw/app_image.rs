@@ -0,0 +1,120 @@
+/*
+* Copyright (c) 2025 luxreduxdelux
+*
+* Redistribution and use in source and binary forms, with or without
+* modification, are permitted provided that the following conditions are met:
+*
+* 1. Redistributions of source code must retain the above copyright notice,
+* this list of conditions and the following disclaimer.
+*
+* 2. Redistributions in binary form must reproduce the above copyright notice,
+* this list of conditions and the following disclaimer in the documentation
+* and/or other materials provided with the distribution.
+*
+* Subject to the terms and conditions of this license, each copyright holder
+* and contributor hereby grants to those receiving rights under this license
+* a perpetual, worldwide, non-exclusive, no-charge, royalty-free, irrevocable
+* (except for failure to satisfy the conditions of this license) patent license
+* to make, have made, use, offer to sell, sell, import, and otherwise transfer
+* this software, where such license applies only to those patent claims, already
+* acquired or hereafter acquired, licensable by such copyright holder or
+* contributor that are necessarily infringed by:
+*
+* (a) their Contribution(s) (the licensed copyrights of copyright holders and
+* non-copyrightable additions of contributors, in source or binary form) alone;
+* or
+*
+* (b) combination of their Contribution(s) with the work of authorship to which
+* such Contribution(s) was added by such copyright holder or contributor, if,
+* at the time the Contribution is added, such addition causes such combination
+* to be necessarily infringed. The patent license shall not apply to any other
+* combinations which include the Contribution.
+*
+* Except as expressly stated above, no rights or licenses from any copyright
+* holder or contributor is granted under this license, whether expressly, by
+* implication, estoppel or otherwise.
+*
+* DISCLAIMER
+*
+* THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+* AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+* IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+* DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS OR CONTRIBUTORS BE LIABLE
+* FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+* DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+* SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+* CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+* OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+* OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+*/
+
+use crate::{
+    draw::{self, Draw},
+    editor::Editor,
+};
+
+//================================================================
+
+use boondle_core::{
+    exporter::{app_image::AppImage, export::format_name},
+    project::Meta,
+};
+use eframe::egui::{self, CollapsingHeader};
+
+//================================================================
+
+impl Draw for AppImage {
+    fn label(&self) -> String {
+        format_name("AppImage (.AppImage)", &self.name)
+    }
+
+    fn draw_setup(&mut self, ui: &mut egui::Ui, meta: &Result<Meta, String>) {
+        let header = CollapsingHeader::new(self.label()).id_salt("app_image");
+
+        header.show(ui, |ui| {
+            ui.checkbox(&mut self.enable, "Enable");
+
+            ui.add_enabled_ui(self.enable, |ui| {
+                Editor::entry_label(ui, &mut self.name, "Name");
+                Editor::entry_list(ui, &mut self.need, "Need");
+                Editor::entry_timeout(ui, &mut self.timeout);
+                Editor::entry_label(ui, &mut self.file, "File");
+
+                Editor::pick_file(ui, "Binary", &mut self.binary);
+                Editor::entry_label(ui, &mut self.binary_from, "Binary From Step");
+                Editor::pick_file(ui, "AppRun Script", &mut self.script);
+                //self.desktop.draw(ui);
+            });
+
+            draw::draw_preview(ui, self, meta);
+
+            ui.separator();
+
+            if ui.button("Remove").clicked() {
+                self.remove = true;
+            }
+        });
+    }
+
+    fn file(&self, meta: &Meta) -> Vec<(String, String)> {
+        let mut result = Vec::new();
+
+        // a custom AppRun script is copied as is, so only its path is shown rather than reading it
+        // from disk on every frame.
+        if self.script.is_empty() {
+            result.push(("AppRun".to_string(), AppImage::file_app_run(meta)));
+        } else if let Ok(script) = meta.template.format(&self.script) {
+            result.push((
+                "AppRun".to_string(),
+                format!("Copied from \"{}\".", meta.resolve(&script).display()),
+            ));
+        }
+
+        result.push((
+            format!("{}.desktop", meta.name),
+            meta.create_desktop_file(true),
+        ));
+
+        result
+    }
+}
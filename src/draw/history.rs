@@ -0,0 +1,150 @@
+/*
+* Copyright (c) 2025 luxreduxdelux
+*
+* Redistribution and use in source and binary forms, with or without
+* modification, are permitted provided that the following conditions are met:
+*
+* 1. Redistributions of source code must retain the above copyright notice,
+* this list of conditions and the following disclaimer.
+*
+* 2. Redistributions in binary form must reproduce the above copyright notice,
+* this list of conditions and the following disclaimer in the documentation
+* and/or other materials provided with the distribution.
+*
+* Subject to the terms and conditions of this license, each copyright holder
+* and contributor hereby grants to those receiving rights under this license
+* a perpetual, worldwide, non-exclusive, no-charge, royalty-free, irrevocable
+* (except for failure to satisfy the conditions of this license) patent license
+* to make, have made, use, offer to sell, sell, import, and otherwise transfer
+* this software, where such license applies only to those patent claims, already
+* acquired or hereafter acquired, licensable by such copyright holder or
+* contributor that are necessarily infringed by:
+*
+* (a) their Contribution(s) (the licensed copyrights of copyright holders and
+* non-copyrightable additions of contributors, in source or binary form) alone;
+* or
+*
+* (b) combination of their Contribution(s) with the work of authorship to which
+* such Contribution(s) was added by such copyright holder or contributor, if,
+* at the time the Contribution is added, such addition causes such combination
+* to be necessarily infringed. The patent license shall not apply to any other
+* combinations which include the Contribution.
+*
+* Except as expressly stated above, no rights or licenses from any copyright
+* holder or contributor is granted under this license, whether expressly, by
+* implication, estoppel or otherwise.
+*
+* DISCLAIMER
+*
+* THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+* AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+* IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+* DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS OR CONTRIBUTORS BE LIABLE
+* FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+* DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+* SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+* CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+* OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+* OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+*/
+
+use boondle_core::history::Run;
+use eframe::egui::{self, CollapsingHeader, Color32, RichText};
+
+//================================================================
+
+/// draw a past run, with the status and log of each of its steps.
+pub fn draw(ui: &mut egui::Ui, run: &mut Run) {
+    let (result, color) = if run.success {
+        ("Success", Color32::LIGHT_GREEN)
+    } else {
+        ("Failure", Color32::LIGHT_RED)
+    };
+
+    let header = RichText::new(format!("{} - {} - {result}", run.date, run.stage)).color(color);
+
+    CollapsingHeader::new(header)
+        .id_salt(run.name())
+        .show(ui, |ui| {
+            if !run.profile.is_empty() {
+                ui.label(format!("Profile: {}", run.profile));
+            }
+
+            ui.label(format!("Duration: {:.1}s", run.duration));
+            ui.label(format!("Folder: {}", run.path.display()));
+
+            ui.separator();
+
+            for (i, step) in run.step.iter_mut().enumerate() {
+                let path = (!step.log.is_empty()).then(|| run.path.join(&step.log));
+
+                ui.push_id(i, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(if step.name.is_empty() {
+                            step.kind.clone()
+                        } else {
+                            format!("{} ({})", step.name, step.kind)
+                        });
+
+                        let color = if step.success {
+                            Color32::LIGHT_GREEN
+                        } else {
+                            Color32::LIGHT_RED
+                        };
+
+                        ui.label(RichText::new(&step.status).color(color));
+
+                        if let Some(code) = step.code {
+                            ui.label(format!("Exit Code: {code}"));
+                        }
+
+                        if let Some(duration) = step.duration {
+                            ui.label(format!("{duration:.1}s"));
+                        }
+
+                        if let Some(path) = &path
+                            && ui.button("Log").clicked()
+                        {
+                            step.text = match step.text {
+                                Some(_) => None,
+                                None => Some(std::fs::read_to_string(path).unwrap_or_default()),
+                            };
+                        }
+                    });
+
+                    if let Some(text) = &step.text {
+                        egui::ScrollArea::vertical()
+                            .max_height(240.0)
+                            .auto_shrink([false, true])
+                            .show(ui, |ui| {
+                                for line in text.lines() {
+                                    if let Some(line) = line.strip_prefix(Run::STDERR) {
+                                        ui.label(
+                                            RichText::new(line)
+                                                .monospace()
+                                                .color(Color32::LIGHT_RED),
+                                        );
+                                    } else {
+                                        ui.label(RichText::new(line).monospace());
+                                    }
+                                }
+                            });
+                    }
+                });
+            }
+
+            if !run.artifact.is_empty() {
+                ui.separator();
+
+                for artifact in &run.artifact {
+                    ui.label(format!(
+                        "{} ({}, {}): {}",
+                        artifact.step,
+                        artifact.kind,
+                        artifact.architecture,
+                        artifact.path.display()
+                    ));
+                }
+            }
+        });
+}
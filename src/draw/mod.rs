@@ -0,0 +1,279 @@
+/*
+* Copyright (c) 2025 luxreduxdelux
+*
+* Redistribution and use in source and binary forms, with or without
+* modification, are permitted provided that the following conditions are met:
+*
+* 1. Redistributions of source code must retain the above copyright notice,
+* this list of conditions and the following disclaimer.
+*
+* 2. Redistributions in binary form must reproduce the above copyright notice,
+* this list of conditions and the following disclaimer in the documentation
+* and/or other materials provided with the distribution.
+*
+* Subject to the terms and conditions of this license, each copyright holder
+* and contributor hereby grants to those receiving rights under this license
+* a perpetual, worldwide, non-exclusive, no-charge, royalty-free, irrevocable
+* (except for failure to satisfy the conditions of this license) patent license
+* to make, have made, use, offer to sell, sell, import, and otherwise transfer
+* this software, where such license applies only to those patent claims, already
+* acquired or hereafter acquired, licensable by such copyright holder or
+* contributor that are necessarily infringed by:
+*
+* (a) their Contribution(s) (the licensed copyrights of copyright holders and
+* non-copyrightable additions of contributors, in source or binary form) alone;
+* or
+*
+* (b) combination of their Contribution(s) with the work of authorship to which
+* such Contribution(s) was added by such copyright holder or contributor, if,
+* at the time the Contribution is added, such addition causes such combination
+* to be necessarily infringed. The patent license shall not apply to any other
+* combinations which include the Contribution.
+*
+* Except as expressly stated above, no rights or licenses from any copyright
+* holder or contributor is granted under this license, whether expressly, by
+* implication, estoppel or otherwise.
+*
+* DISCLAIMER
+*
+* THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+* AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+* IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+* DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS OR CONTRIBUTORS BE LIABLE
+* FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+* DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+* SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+* CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+* OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+* OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+*/
+
+pub mod app_image;
+pub mod cargo;
+pub mod checksum;
+pub mod debian;
+pub mod history;
+pub mod script;
+pub mod sign;
+
+//================================================================
+
+use crate::editor::Editor;
+
+//================================================================
+
+use boondle_core::{
+    exporter::{
+        app_image::AppImage,
+        cargo::Cargo,
+        checksum::Checksum,
+        debian::Debian,
+        export::{Export, ExportStatus, kind},
+        plan::Plan,
+        script::Script,
+        sign::Sign,
+    },
+    project::Meta,
+};
+
+//================================================================
+
+use eframe::egui::{self, CollapsingHeader, Color32, RichText, ScrollArea};
+use std::time::Instant;
+
+//================================================================
+
+/// the GUI of an exporter.
+pub trait Draw {
+    /// get the label of the exporter, with its name.
+    fn label(&self) -> String;
+    /// draw the exporter's set-up, in the project view. meta is the resolved project metadata, or
+    /// the error that kept it from being resolved.
+    fn draw_setup(&mut self, ui: &mut egui::Ui, meta: &Result<Meta, String>);
+
+    /// get every file the exporter generates, by name, from its current fields.
+    fn file(&self, _meta: &Meta) -> Vec<(String, String)> {
+        Vec::default()
+    }
+}
+
+/// get the GUI of a step, if its exporter has one.
+pub fn step(step: &mut dyn Export) -> Option<&mut dyn Draw> {
+    let step = step.as_any_mut();
+
+    if step.is::<AppImage>() {
+        step.downcast_mut::<AppImage>().map(|x| x as &mut dyn Draw)
+    } else if step.is::<Cargo>() {
+        step.downcast_mut::<Cargo>().map(|x| x as &mut dyn Draw)
+    } else if step.is::<Checksum>() {
+        step.downcast_mut::<Checksum>().map(|x| x as &mut dyn Draw)
+    } else if step.is::<Debian>() {
+        step.downcast_mut::<Debian>().map(|x| x as &mut dyn Draw)
+    } else if step.is::<Sign>() {
+        step.downcast_mut::<Sign>().map(|x| x as &mut dyn Draw)
+    } else {
+        step.downcast_mut::<Script>().map(|x| x as &mut dyn Draw)
+    }
+}
+
+/// draw the set-up of a step.
+pub fn draw_setup(ui: &mut egui::Ui, export: &mut dyn Export, meta: &Result<Meta, String>) {
+    match step(export) {
+        Some(draw) => draw.draw_setup(ui, meta),
+        None => {
+            ui.label(kind(export));
+        }
+    }
+}
+
+/// draw a read-only preview of every file a step generates, live from its current fields, with
+/// every problem its validation finds.
+pub fn draw_preview<T: Export + Draw>(ui: &mut egui::Ui, step: &T, meta: &Result<Meta, String>) {
+    CollapsingHeader::new("Preview")
+        .id_salt(ui.id().with("preview"))
+        .show(ui, |ui| {
+            let meta = match meta {
+                Ok(meta) => meta,
+                Err(error) => {
+                    ui.label(RichText::new(error).color(Color32::LIGHT_RED));
+                    return;
+                }
+            };
+
+            // validation scans PATH and reads files, so its result is kept for a while.
+            let id = ui.id().with("validate");
+            let problem = match ui.data(|x| x.get_temp::<(Instant, Vec<String>)>(id)) {
+                Some((time, problem)) if time.elapsed() < Editor::REFRESH => problem,
+                _ => {
+                    let problem = step.validate(meta);
+                    ui.data_mut(|x| x.insert_temp(id, (Instant::now(), problem.clone())));
+                    problem
+                }
+            };
+
+            if problem.is_empty() {
+                ui.label(RichText::new("No problem found.").color(Color32::LIGHT_GREEN));
+            }
+
+            for problem in problem {
+                ui.label(RichText::new(format!("Warning: {problem}")).color(Color32::YELLOW));
+            }
+
+            for (name, data) in step.file(meta) {
+                ui.separator();
+                ui.label(RichText::new(name).strong());
+                ui.add(
+                    egui::TextEdit::multiline(&mut data.as_str())
+                        .code_editor()
+                        .desired_width(f32::INFINITY),
+                );
+            }
+        });
+}
+
+/// draw the status of a step, in the compile or package window.
+pub fn draw_modal(ui: &mut egui::Ui, export: &mut dyn Export) {
+    let label = step(export)
+        .map(|draw| draw.label())
+        .unwrap_or_else(|| kind(export));
+    let status = export.get_status();
+
+    if *status != ExportStatus::Skipped {
+        ui.horizontal(|ui| {
+            ui.label(label);
+            ui.label(RichText::new(format!("{status}")).color(status_color(status)));
+
+            if *status == ExportStatus::InProgress {
+                ui.spinner();
+            }
+        });
+    }
+}
+
+/// draw the progress of a step while it runs, and its warnings.
+pub fn draw_progress(ui: &mut egui::Ui, export: &mut dyn Export) {
+    if *export.get_status() == ExportStatus::InProgress
+        && let Some(progress) = export.get_progress().last().cloned()
+    {
+        let bar = egui::ProgressBar::new(progress.fraction.unwrap_or_default())
+            .text(progress.message)
+            .animate(progress.fraction.is_none());
+
+        ui.add(bar);
+    }
+
+    for warning in export.get_warning() {
+        ui.label(RichText::new(format!("Warning: {warning}")).color(Color32::YELLOW));
+    }
+}
+
+/// draw the output of a step's command, keeping only the lines that contain the search text.
+pub fn draw_log(ui: &mut egui::Ui, export: &mut dyn Export, search: &str) {
+    let log = export.get_log();
+
+    if log.is_empty() {
+        return;
+    }
+
+    let search = search.to_lowercase();
+
+    CollapsingHeader::new(format!("Log ({} lines)", log.len()))
+        .id_salt(ui.id().with("log"))
+        .show(ui, |ui| {
+            ScrollArea::vertical()
+                .max_height(240.0)
+                .stick_to_bottom(true)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    for line in log {
+                        if !search.is_empty() && !line.text.to_lowercase().contains(&search) {
+                            continue;
+                        }
+
+                        let text = RichText::new(&line.text).monospace();
+
+                        ui.label(if line.stderr {
+                            text.color(Color32::LIGHT_RED)
+                        } else {
+                            text
+                        });
+                    }
+                });
+        });
+}
+
+/// draw everything a step would do when run.
+pub fn draw_plan(ui: &mut egui::Ui, plan: &Plan) {
+    let label = if plan.step.is_empty() {
+        plan.kind.clone()
+    } else {
+        format!("{} ({})", plan.step, plan.kind)
+    };
+
+    CollapsingHeader::new(label)
+        .default_open(true)
+        .show(ui, |ui| {
+            for line in plan.to_string().lines() {
+                let text = RichText::new(line).monospace();
+
+                ui.label(if line.starts_with("Warning: ") {
+                    text.color(Color32::YELLOW)
+                } else {
+                    text
+                });
+            }
+        });
+}
+
+/// get the color to draw a status with.
+pub fn status_color(status: &ExportStatus) -> Color32 {
+    match status {
+        ExportStatus::InProgress => Color32::LIGHT_BLUE,
+        ExportStatus::Waiting => Color32::LIGHT_GRAY,
+        ExportStatus::Success => Color32::LIGHT_GREEN,
+        ExportStatus::Failure(_) => Color32::LIGHT_RED,
+        ExportStatus::Skipped => Color32::GRAY,
+        ExportStatus::Cancelled => Color32::YELLOW,
+        ExportStatus::TimedOut => Color32::LIGHT_RED,
+    }
+}
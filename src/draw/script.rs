@@ -49,74 +49,83 @@
 */
 
 use crate::{
-    app::App,
-    exporter::export::*,
-    project::{Meta, Project},
+    draw::{self, Draw},
+    editor::Editor,
 };
 
 //================================================================
 
-use eframe::egui::{self, CollapsingHeader, RichText};
-use serde::{Deserialize, Serialize};
+use boondle_core::{
+    exporter::{
+        artifact::ArtifactKind,
+        export::format_name,
+        script::{Layout, Output, Script},
+    },
+    project::Meta,
+};
+use eframe::egui::{self, CollapsingHeader};
 
 //================================================================
 
-#[derive(Serialize, Deserialize)]
-#[serde(tag = "kind")]
-enum Layout {
-    String {
-        name: String,
-        data: String,
-        #[serde(skip)]
-        remove: bool,
-    },
-    Integer {
-        name: String,
-        data: i64,
-        #[serde(skip)]
-        remove: bool,
-    },
-    Decimal {
-        name: String,
-        data: f64,
-        #[serde(skip)]
-        remove: bool,
-    },
-    Boolean {
-        name: String,
-        data: bool,
-        #[serde(skip)]
-        remove: bool,
-    },
-}
-
-#[derive(Default, Serialize, Deserialize)]
-pub struct Script {
-    name: String,
-    script: String,
-    layout: Vec<Layout>,
-    enable: bool,
-    #[serde(skip)]
-    remove: bool,
-    #[serde(skip)]
-    status: ExportStatus,
-    #[serde(skip)]
-    handler: EventHandler,
-}
+impl Draw for Script {
+    fn label(&self) -> String {
+        format_name("Custom Script", &self.name)
+    }
 
-#[typetag::serde]
-impl Export for Script {
-    fn draw_setup(&mut self, ui: &mut egui::Ui) {
-        let header = CollapsingHeader::new(format_name("Custom Script", &self.name))
-            .id_salt("custom_script");
+    fn draw_setup(&mut self, ui: &mut egui::Ui, meta: &Result<Meta, String>) {
+        let header = CollapsingHeader::new(self.label()).id_salt("custom_script");
 
         header.show(ui, |ui| {
             ui.checkbox(&mut self.enable, "Enable");
 
             ui.add_enabled_ui(self.enable, |ui| {
-                Project::entry_label(ui, &mut self.name, "Name");
+                Editor::entry_label(ui, &mut self.name, "Name");
+                Editor::entry_list(ui, &mut self.need, "Need");
+                Editor::entry_timeout(ui, &mut self.timeout);
+
+                Editor::pick_file(ui, "Script", &mut self.script);
 
-                Project::pick_file(ui, "Script", &mut self.script);
+                ui.separator();
+
+                if ui.button("+ Output").clicked() {
+                    self.output.push(Output::default());
+                }
+
+                self.output.retain(|output| !output.remove);
+
+                for (i, output) in self.output.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.add(egui::TextEdit::singleline(&mut output.path).hint_text("Path"));
+
+                        egui::ComboBox::from_id_salt(("script_output", i))
+                            .selected_text(format!("{:?}", output.kind))
+                            .show_ui(ui, |ui| {
+                                for kind in [
+                                    ArtifactKind::Binary,
+                                    ArtifactKind::Package,
+                                    ArtifactKind::Other,
+                                ] {
+                                    ui.selectable_value(
+                                        &mut output.kind,
+                                        kind,
+                                        format!("{kind:?}"),
+                                    );
+                                }
+                            });
+
+                        let hint = output.architecture().to_string();
+
+                        ui.add(
+                            egui::TextEdit::singleline(&mut output.architecture)
+                                .hint_text(hint)
+                                .desired_width(64.0),
+                        );
+
+                        if ui.button("Remove").clicked() {
+                            output.remove = true;
+                        }
+                    });
+                }
 
                 ui.separator();
 
@@ -163,11 +172,7 @@ impl Export for Script {
 
                 for widget in &mut self.layout {
                     match widget {
-                        &mut Layout::String {
-                            ref mut name,
-                            ref mut data,
-                            ref mut remove,
-                        } => {
+                        Layout::String { name, data, remove } => {
                             ui.label(name.as_str());
                             ui.text_edit_singleline(data).context_menu(|ui| {
                                 ui.label("Name");
@@ -178,11 +183,7 @@ impl Export for Script {
                                 }
                             });
                         }
-                        &mut Layout::Integer {
-                            ref mut name,
-                            ref mut data,
-                            ref mut remove,
-                        } => {
+                        Layout::Integer { name, data, remove } => {
                             ui.label(name.as_str());
 
                             ui.add(egui::DragValue::new(data).speed(0.1))
@@ -195,11 +196,7 @@ impl Export for Script {
                                     }
                                 });
                         }
-                        &mut Layout::Decimal {
-                            ref mut name,
-                            ref mut data,
-                            ref mut remove,
-                        } => {
+                        Layout::Decimal { name, data, remove } => {
                             ui.label(name.as_str());
 
                             ui.add(egui::DragValue::new(data).speed(0.1))
@@ -212,11 +209,7 @@ impl Export for Script {
                                     }
                                 });
                         }
-                        &mut Layout::Boolean {
-                            ref mut name,
-                            ref mut data,
-                            ref mut remove,
-                        } => {
+                        Layout::Boolean { name, data, remove } => {
                             ui.checkbox(data, name.as_str()).context_menu(|ui| {
                                 ui.label("Name");
                                 ui.text_edit_singleline(name);
@@ -230,6 +223,8 @@ impl Export for Script {
                 }
             });
 
+            draw::draw_preview(ui, self, meta);
+
             ui.separator();
 
             if ui.button("Remove").clicked() {
@@ -237,89 +232,4 @@ impl Export for Script {
             }
         });
     }
-
-    fn draw_modal(&mut self, ui: &mut egui::Ui) {
-        if self.enable {
-            ui.horizontal(|ui| {
-                ui.label(format_name("Custom Script", &self.name));
-                ui.label(RichText::new(format!("{}", self.status)).color(self.status.color()));
-
-                if self.status == ExportStatus::InProgress {
-                    ui.spinner();
-                }
-            });
-        }
-    }
-
-    fn get_enable(&self) -> bool {
-        self.enable
-    }
-
-    fn get_remove(&self) -> bool {
-        self.remove
-    }
-
-    fn get_status(&mut self) -> &mut ExportStatus {
-        &mut self.status
-    }
-
-    fn get_handler(&mut self) -> &mut EventHandler {
-        &mut self.handler
-    }
-
-    fn run(&mut self, meta: Meta) -> anyhow::Result<()> {
-        if !self.enable {
-            return Ok(());
-        }
-
-        self.status = ExportStatus::InProgress;
-
-        if meta.name.is_empty() {
-            return Err(anyhow::Error::msg(
-                "Custom Script: Project name cannot be empty.",
-            ));
-        }
-
-        if meta.version.is_empty() {
-            return Err(anyhow::Error::msg(
-                "Custom Script: Project version cannot be empty.",
-            ));
-        }
-
-        if !self.script.is_empty() {
-            let mut command = std::process::Command::new(&self.script);
-
-            // TO-DO move into own function?
-            command.env("BOONDLE_NAME", meta.name);
-            command.env("BOONDLE_ICON", meta.icon);
-            command.env("BOONDLE_INFO", meta.info);
-            command.env("BOONDLE_FROM", meta.from);
-            command.env("BOONDLE_VERSION", meta.version);
-            command.env("BOONDLE_NAME_GENERIC", meta.name_generic);
-            command.env("BOONDLE_NAME_COMMENT", meta.comment);
-            command.env("BOONDLE_NAME_CATEGORY", meta.category);
-            command.env("BOONDLE_NAME_KEY_WORD", meta.key_word);
-
-            for widget in &self.layout {
-                match widget {
-                    Layout::String { name, data, .. } => command.env(name.to_uppercase(), data),
-                    Layout::Integer { name, data, .. } => {
-                        command.env(name.to_uppercase(), data.to_string())
-                    }
-                    Layout::Decimal { name, data, .. } => {
-                        command.env(name.to_uppercase(), data.to_string())
-                    }
-                    Layout::Boolean { name, data, .. } => {
-                        command.env(name.to_uppercase(), if *data { "1" } else { "0" })
-                    }
-                };
-            }
-
-            self.execute(command);
-        } else {
-            self.status = ExportStatus::Success;
-        }
-
-        Ok(())
-    }
 }
@@ -0,0 +1,601 @@
+/*
+* Copyright (c) 2025 luxreduxdelux
+*
+* Redistribution and use in source and binary forms, with or without
+* modification, are permitted provided that the following conditions are met:
+*
+* 1. Redistributions of source code must retain the above copyright notice,
+* this list of conditions and the following disclaimer.
+*
+* 2. Redistributions in binary form must reproduce the above copyright notice,
+* this list of conditions and the following disclaimer in the documentation
+* and/or other materials provided with the distribution.
+*
+* Subject to the terms and conditions of this license, each copyright holder
+* and contributor hereby grants to those receiving rights under this license
+* a perpetual, worldwide, non-exclusive, no-charge, royalty-free, irrevocable
+* (except for failure to satisfy the conditions of this license) patent license
+* to make, have made, use, offer to sell, sell, import, and otherwise transfer
+* this software, where such license applies only to those patent claims, already
+* acquired or hereafter acquired, licensable by such copyright holder or
+* contributor that are necessarily infringed by:
+*
+* (a) their Contribution(s) (the licensed copyrights of copyright holders and
+* non-copyrightable additions of contributors, in source or binary form) alone;
+* or
+*
+* (b) combination of their Contribution(s) with the work of authorship to which
+* such Contribution(s) was added by such copyright holder or contributor, if,
+* at the time the Contribution is added, such addition causes such combination
+* to be necessarily infringed. The patent license shall not apply to any other
+* combinations which include the Contribution.
+*
+* Except as expressly stated above, no rights or licenses from any copyright
+* holder or contributor is granted under this license, whether expressly, by
+* implication, estoppel or otherwise.
+*
+* DISCLAIMER
+*
+* THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+* AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+* IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+* DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDERS OR CONTRIBUTORS BE LIABLE
+* FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+* DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+* SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+* CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+* OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+* OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+*/
+
+use crate::{app::App, draw};
+
+//================================================================
+
+use boondle_core::{
+    exporter::{
+        app_image::AppImage,
+        cargo::Cargo,
+        checksum::Checksum,
+        debian::Debian,
+        plan::Plan,
+        script::Script,
+        sign::{Sign, SignTool},
+    },
+    history::Run,
+    project::{Meta, Profile, Project},
+};
+use eframe::egui::{self, Color32, Response, RichText};
+use egui_modal::Modal;
+use std::time::{Duration, Instant};
+
+//================================================================
+
+/// an open project, with the state of its GUI.
+pub struct Editor {
+    pub project: Project,
+    log_search: String,
+    /// past runs, with a warning for every run that could not be read.
+    history: Option<(Vec<Run>, Vec<String>)>,
+    /// what the compile and package stages would do, while the preview window is open.
+    preview: Option<(Vec<Plan>, Vec<Plan>)>,
+    /// resolved project metadata for the set-up previews.
+    meta: Result<Meta, String>,
+    meta_time: Option<Instant>,
+}
+
+impl Editor {
+    /// how often the set-up previews are brought up to date, as doing so may read files, the Cargo
+    /// manifest, PATH or run git.
+    pub const REFRESH: Duration = Duration::from_secs(1);
+
+    pub fn new(project: Project) -> Self {
+        Self {
+            project,
+            log_search: String::default(),
+            history: None,
+            preview: None,
+            meta: Err(String::default()),
+            meta_time: None,
+        }
+    }
+
+    pub fn draw(&mut self, context: &egui::Context) {
+        if !App::error(self.project.poll(), "History Error").unwrap_or_default() {
+            context.request_repaint_after(Duration::from_millis(50));
+        }
+
+        self.meta_refresh();
+
+        egui::TopBottomPanel::top("layout").show(context, |ui| {
+            egui::MenuBar::new().ui(ui, |ui| {
+                ui.menu_button("File", |ui| {
+                    if ui.button("New").clicked()
+                        && let Some(path) = rfd::FileDialog::new().pick_folder()
+                        && let Ok(data) = App::error(Project::create(&path), "New Error")
+                    {
+                        *self = Self::new(data);
+                    };
+
+                    if ui.button("Save").clicked() {
+                        let _ = App::error(self.project.save(), "Save Error");
+                    };
+
+                    if ui.button("Load").clicked()
+                        && let Some(path) = rfd::FileDialog::new().pick_file()
+                        && let Ok(data) = App::error(Project::load(path), "Load Error")
+                    {
+                        *self = Self::new(data);
+                    };
+
+                    ui.separator();
+
+                    let idle = self.project.schedule_compile.is_none()
+                        && self.project.schedule_package.is_none();
+
+                    if Self::button_enable(ui, idle, "Clean").clicked() {
+                        let _ = App::error(self.project.clean(false), "Clean Error");
+                    }
+
+                    if Self::button_enable(ui, idle, "Clean With Packages").clicked() {
+                        let _ = App::error(self.project.clean(true), "Clean Error");
+                    }
+                });
+
+                if ui.button("History").clicked() {
+                    self.history =
+                        App::error(Run::list(&self.project.meta.path), "History Error").ok();
+                }
+
+                ui.separator();
+
+                ui.add(
+                    egui::DragValue::new(&mut self.project.job)
+                        .prefix("Jobs: ")
+                        .custom_formatter(|value, _| {
+                            if value == 0.0 {
+                                "Auto".to_string()
+                            } else {
+                                value.to_string()
+                            }
+                        }),
+                );
+
+                if !self.project.profile.is_empty() {
+                    ui.separator();
+
+                    egui::ComboBox::from_label("Profile")
+                        .selected_text(if self.project.profile_active.is_empty() {
+                            "(None)"
+                        } else {
+                            &self.project.profile_active
+                        })
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
+                                &mut self.project.profile_active,
+                                String::default(),
+                                "(None)",
+                            );
+
+                            for name in self.project.profile.keys() {
+                                ui.selectable_value(
+                                    &mut self.project.profile_active,
+                                    name.clone(),
+                                    name,
+                                );
+                            }
+                        });
+                }
+            });
+        });
+
+        if let Some(history) = &mut self.history {
+            let mut open = true;
+
+            egui::Window::new("History")
+                .open(&mut open)
+                .default_size((640.0, 480.0))
+                .show(context, |ui| {
+                    if ui.button("Refresh").clicked()
+                        && let Ok(list) =
+                            App::error(Run::list(&self.project.meta.path), "History Error")
+                    {
+                        *history = list;
+                    }
+
+                    for warning in &history.1 {
+                        ui.label(
+                            RichText::new(format!("Warning: {warning}")).color(Color32::YELLOW),
+                        );
+                    }
+
+                    ui.separator();
+
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        if history.0.is_empty() {
+                            ui.label("No run yet.");
+                        }
+
+                        for (i, run) in history.0.iter_mut().enumerate() {
+                            ui.push_id(i, |ui| draw::history::draw(ui, run));
+                        }
+                    });
+                });
+
+            if !open {
+                self.history = None;
+            }
+        }
+
+        if let Some((compile, package)) = &mut self.preview {
+            let mut open = true;
+
+            egui::Window::new("Preview")
+                .open(&mut open)
+                .default_size((640.0, 480.0))
+                .show(context, |ui| {
+                    if ui.button("Refresh").clicked()
+                        && let Ok(preview) =
+                            App::error(Self::preview(&self.project), "Preview Error")
+                    {
+                        (*compile, *package) = preview;
+                    }
+
+                    ui.separator();
+
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        for (label, list) in [("Compile", &*compile), ("Package", &*package)] {
+                            ui.collapsing(label, |ui| {
+                                if list.is_empty() {
+                                    ui.label("No step to run.");
+                                }
+
+                                for (i, plan) in list.iter().enumerate() {
+                                    ui.push_id(i, |ui| draw::draw_plan(ui, plan));
+                                }
+                            });
+                        }
+                    });
+                });
+
+            if !open {
+                self.preview = None;
+            }
+        }
+
+        egui::CentralPanel::default().show(context, |ui| {
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                self.draw_project(ui);
+                self.draw_variable(ui);
+                self.draw_compile(ui);
+                self.draw_package(ui);
+
+                //================================================================
+
+                let mut cancel = false;
+                let modal_compile = Modal::new(context, "modal_compile");
+
+                modal_compile.show(|ui| {
+                    modal_compile.title(ui, "Compile");
+
+                    modal_compile.frame(ui, |ui| {
+                        Self::entry_label(ui, &mut self.log_search, "Search Log");
+
+                        for (i, compile) in self.project.compile.iter_mut().enumerate() {
+                            ui.push_id(i, |ui| {
+                                draw::draw_modal(ui, compile.as_mut());
+                                draw::draw_progress(ui, compile.as_mut());
+                                draw::draw_log(ui, compile.as_mut(), &self.log_search);
+                            });
+                        }
+                    });
+
+                    let complete_compile = self
+                        .project
+                        .compile
+                        .iter_mut()
+                        .all(|compile| compile.success_or_failure());
+
+                    modal_compile.buttons(ui, |ui| {
+                        if complete_compile {
+                            if modal_compile.button(ui, "Close").clicked() {};
+                        } else if ui.button("Cancel").clicked() {
+                            cancel = true;
+                        }
+                    });
+                });
+
+                //================================================================
+
+                let modal_package = Modal::new(context, "modal_package");
+
+                modal_package.show(|ui| {
+                    modal_compile.title(ui, "Package");
+
+                    modal_package.frame(ui, |ui| {
+                        Self::entry_label(ui, &mut self.log_search, "Search Log");
+
+                        for (i, package) in self.project.package.iter_mut().enumerate() {
+                            ui.push_id(i, |ui| {
+                                draw::draw_modal(ui, package.as_mut());
+                                draw::draw_progress(ui, package.as_mut());
+                                draw::draw_log(ui, package.as_mut(), &self.log_search);
+                            });
+                        }
+                    });
+
+                    let complete_package = self
+                        .project
+                        .package
+                        .iter_mut()
+                        .all(|package| package.success_or_failure());
+
+                    modal_package.buttons(ui, |ui| {
+                        if complete_package {
+                            if modal_package.button(ui, "Close").clicked() {};
+                        } else if ui.button("Cancel").clicked() {
+                            cancel = true;
+                        }
+                    });
+                });
+
+                if cancel {
+                    self.project.cancel();
+                }
+
+                //================================================================
+
+                let profile = self.project.profile_get().ok().flatten();
+                let can_compile = self.project.schedule_compile.is_none()
+                    && self
+                        .project
+                        .compile
+                        .iter()
+                        .any(|compile| Profile::step_enable(profile, compile.as_ref()));
+                let can_package = self.project.schedule_package.is_none()
+                    && self
+                        .project
+                        .package
+                        .iter()
+                        .any(|package| Profile::step_enable(profile, package.as_ref()));
+
+                if Self::button_enable(ui, can_compile, "Compile").clicked()
+                    && App::error(self.project.compile(), "Compile Error").is_ok()
+                {
+                    modal_compile.open();
+                }
+
+                if Self::button_enable(ui, can_package, "Package").clicked()
+                    && App::error(self.project.package(), "Package Error").is_ok()
+                {
+                    modal_package.open();
+                }
+
+                if ui.button("Preview").clicked() {
+                    self.preview = App::error(Self::preview(&self.project), "Preview Error").ok();
+                }
+            });
+        });
+    }
+
+    /// resolve the project metadata again for the set-up previews, at most once a second, as it
+    /// may read the Cargo manifest or run git.
+    fn meta_refresh(&mut self) {
+        if self
+            .meta_time
+            .is_none_or(|time| time.elapsed() >= Self::REFRESH)
+        {
+            self.meta = self.project.meta_resolve().map_err(|x| x.to_string());
+            self.meta_time = Some(Instant::now());
+        }
+    }
+
+    /// get what the compile and package stages would do, without running them.
+    fn preview(project: &Project) -> anyhow::Result<(Vec<Plan>, Vec<Plan>)> {
+        Ok((project.preview(false)?, project.preview(true)?))
+    }
+
+    pub fn pick_file(ui: &mut egui::Ui, name: &str, path: &mut String) {
+        ui.horizontal(|ui| {
+            if ui.button(name).clicked()
+                && let Some(file) = rfd::FileDialog::new().pick_file()
+            {
+                *path = file.display().to_string();
+            }
+
+            Self::entry_label(ui, path, "Path");
+        });
+    }
+
+    pub fn entry_label(ui: &mut egui::Ui, text: &mut String, label: &str) {
+        ui.label(label);
+        ui.text_edit_singleline(text);
+    }
+
+    /// edit a step's timeout, in seconds.
+    pub fn entry_timeout(ui: &mut egui::Ui, timeout: &mut u64) {
+        ui.label("Timeout");
+        ui.add(
+            egui::DragValue::new(timeout)
+                .suffix(" s")
+                .custom_formatter(|value, _| {
+                    if value == 0.0 {
+                        "None".to_string()
+                    } else {
+                        value.to_string()
+                    }
+                }),
+        );
+    }
+
+    /// edit a list of strings as comma-separated text.
+    pub fn entry_list(ui: &mut egui::Ui, list: &mut Vec<String>, label: &str) {
+        let mut text = list.join(", ");
+
+        ui.label(label);
+
+        if ui.text_edit_singleline(&mut text).changed() {
+            *list = text
+                .split(',')
+                .map(|x| x.trim().to_string())
+                .filter(|x| !x.is_empty())
+                .collect();
+        }
+    }
+
+    fn button_enable(ui: &mut egui::Ui, enable: bool, label: &str) -> Response {
+        ui.add_enabled(enable, egui::Button::new(label))
+    }
+
+    #[rustfmt::skip]
+    fn draw_project(&mut self, ui: &mut egui::Ui) {
+        ui.collapsing("Project", |ui| {
+            Self::entry_label(ui, &mut self.project.meta.name,         "Name");
+            Self::entry_label(ui, &mut self.project.meta.info,         "Info");
+            Self::entry_label(ui, &mut self.project.meta.from,         "From");
+            Self::entry_label(ui, &mut self.project.meta.version,      "Version");
+            Self::entry_label(ui, &mut self.project.meta.name_generic, "Generic Name");
+            Self::entry_label(ui, &mut self.project.meta.comment,      "Comment");
+            Self::entry_label(ui, &mut self.project.meta.category,     "Category");
+            Self::entry_label(ui, &mut self.project.meta.key_word,     "Key-Word");
+            Self::entry_label(ui, &mut self.project.meta.homepage,     "Homepage");
+            Self::entry_label(ui, &mut self.project.meta.license,      "License");
+
+            Self::pick_file(ui, "Icon",           &mut self.project.meta.icon);
+            Self::pick_file(ui, "Cargo Manifest", &mut self.project.meta.cargo);
+
+            Self::entry_label(ui, &mut self.project.stage,       "Stage Folder");
+            Self::entry_label(ui, &mut self.project.output,      "Output Folder");
+            Self::entry_label(ui, &mut self.project.output_name, "Output Name");
+
+            egui::ComboBox::from_label("Signing Tool")
+                .selected_text(self.project.signing.tool.to_string())
+                .show_ui(ui, |ui| {
+                    for tool in SignTool::LIST {
+                        ui.selectable_value(&mut self.project.signing.tool, tool, tool.to_string());
+                    }
+                });
+
+            Self::entry_label(ui, &mut self.project.signing.key,        "Signing Key");
+            Self::entry_label(ui, &mut self.project.signing.passphrase, "Passphrase Variable");
+
+            Self::pick_file(ui, "Signing Key File", &mut self.project.signing.key_file);
+
+            ui.checkbox(&mut self.project.meta.command_line, "Command-Line Application");
+        });
+    }
+
+    fn draw_variable(&mut self, ui: &mut egui::Ui) {
+        ui.collapsing("Variable", |ui| {
+            if ui.button("+ Variable").clicked() {
+                let key = (0..)
+                    .map(|i| format!("variable_{i}"))
+                    .find(|key| !self.project.variable.contains_key(key))
+                    .unwrap_or_default();
+
+                self.project.variable.insert(key, String::default());
+            };
+
+            ui.separator();
+
+            let mut rename = None;
+            let mut remove = None;
+
+            for (key, value) in &mut self.project.variable {
+                ui.horizontal(|ui| {
+                    ui.label(format!("{{{key}}}")).context_menu(|ui| {
+                        let mut name = key.clone();
+
+                        ui.label("Name");
+
+                        if ui.text_edit_singleline(&mut name).changed() {
+                            rename = Some((key.clone(), name));
+                        }
+
+                        if ui.button("Remove").clicked() {
+                            remove = Some(key.clone());
+                        }
+                    });
+
+                    ui.text_edit_singleline(value);
+                });
+            }
+
+            if let Some((key, name)) = rename
+                && !name.is_empty()
+                && !self.project.variable.contains_key(&name)
+                && let Some(value) = self.project.variable.remove(&key)
+            {
+                self.project.variable.insert(name, value);
+            }
+
+            if let Some(key) = remove {
+                self.project.variable.remove(&key);
+            }
+        });
+    }
+
+    fn draw_compile(&mut self, ui: &mut egui::Ui) {
+        ui.collapsing("Compile", |ui| {
+            ui.horizontal(|ui| {
+                if ui.button("+ Cargo").clicked() {
+                    self.project.compile.push(Box::new(Cargo::default()));
+                };
+
+                if ui.button("+ Custom Script").clicked() {
+                    self.project.compile.push(Box::new(Script::default()));
+                };
+            });
+
+            ui.separator();
+
+            for (i, compile) in self.project.compile.iter_mut().enumerate() {
+                ui.push_id(i, |ui| {
+                    draw::draw_setup(ui, compile.as_mut(), &self.meta);
+                });
+            }
+        });
+
+        if self.project.schedule_compile.is_none() {
+            self.project.compile.retain(|compile| !compile.get_remove());
+        }
+    }
+
+    fn draw_package(&mut self, ui: &mut egui::Ui) {
+        ui.collapsing("Package", |ui| {
+            ui.horizontal(|ui| {
+                if ui.button("+ Debian").clicked() {
+                    self.project.package.push(Box::new(Debian::default()));
+                };
+
+                if ui.button("+ AppImage").clicked() {
+                    self.project.package.push(Box::new(AppImage::default()));
+                };
+
+                if ui.button("+ Custom Script").clicked() {
+                    self.project.package.push(Box::new(Script::default()));
+                };
+
+                if ui.button("+ Sign").clicked() {
+                    self.project.package.push(Box::new(Sign::default()));
+                };
+
+                if ui.button("+ Checksum").clicked() {
+                    self.project.package.push(Box::new(Checksum::default()));
+                };
+            });
+
+            ui.separator();
+
+            for (i, package) in self.project.package.iter_mut().enumerate() {
+                ui.push_id(i, |ui| {
+                    draw::draw_setup(ui, package.as_mut(), &self.meta);
+                });
+            }
+        });
+
+        if self.project.schedule_package.is_none() {
+            self.project.package.retain(|package| !package.get_remove());
+        }
+    }
+}
@@ -1,4 +0,0 @@
-pub mod app_image;
-pub mod debian;
-pub mod export;
-pub mod script;
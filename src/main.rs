@@ -48,15 +48,19 @@
 * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 */
 
-#![feature(exit_status_error)]
-
+#[cfg(feature = "gui")]
 mod app;
-mod exporter;
-mod project;
+#[cfg(feature = "gui")]
+mod draw;
+#[cfg(feature = "gui")]
+mod editor;
+#[cfg(feature = "gui")]
 mod setting;
 mod terminal;
 
-use crate::{app::*, terminal::*};
+#[cfg(feature = "gui")]
+use crate::app::*;
+use crate::terminal::*;
 
 //================================================================
 
@@ -64,6 +68,7 @@ use std::error;
 
 //================================================================
 
+#[cfg(feature = "gui")]
 fn main() -> std::result::Result<(), Box<dyn error::Error>> {
     // check if a command has been sent to us, if it hasn't, spawn GUI.
     if !Terminal::run()? {
@@ -84,3 +89,16 @@ fn main() -> std::result::Result<(), Box<dyn error::Error>> {
 
     Ok(())
 }
+
+#[cfg(not(feature = "gui"))]
+fn main() -> std::result::Result<(), Box<dyn error::Error>> {
+    // without a GUI, a command is required.
+    if !Terminal::run()? {
+        return Err(anyhow::Error::msg(
+            "Terminal: No command given, and this build has no GUI. Run \"boondle --help\" for every command.",
+        )
+        .into());
+    }
+
+    Ok(())
+}
@@ -48,14 +48,38 @@
 * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 */
 
-use crate::project::Project;
+use boondle_core::{
+    exporter::{
+        artifact::ArtifactKind,
+        checksum::{self, Hash, Verdict},
+        export::{ExportStatus, label},
+    },
+    format::Format,
+    history::Run,
+    inspect,
+    project::Project,
+    schema,
+};
+use clap::{Parser, Subcommand};
+use std::{
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
 
 //================================================================
 
-use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+/// what has been printed of a step so far.
+struct Seen {
+    label: String,
+    progress: usize,
+    log: usize,
+    warning: usize,
+    done: bool,
+}
 
-//================================================================
+/// set once the terminal sends an interrupt, while steps are running.
+static INTERRUPT: AtomicBool = AtomicBool::new(false);
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
@@ -71,18 +95,117 @@ enum Command {
         /// Path to project.
         #[arg(short, long)]
         path: PathBuf,
+        /// Profile to apply.
+        #[arg(long)]
+        profile: Option<String>,
+        /// Most steps to run at once, over the project's own. 0 for one per CPU core.
+        #[arg(short, long)]
+        job: Option<usize>,
+        /// Print every file that would be staged and every command that would be run, without running anything.
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Run the package step on the project
     Package {
         /// Path to project.
         #[arg(short, long)]
         path: PathBuf,
+        /// Profile to apply.
+        #[arg(long)]
+        profile: Option<String>,
+        /// Most steps to run at once, over the project's own. 0 for one per CPU core.
+        #[arg(short, long)]
+        job: Option<usize>,
+        /// Print every file that would be staged and every command that would be run, without running anything.
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Run the compile step, then the package step on the project
     CompileExport {
         /// Path to project.
         #[arg(short, long)]
         path: PathBuf,
+        /// Profile to apply.
+        #[arg(long)]
+        profile: Option<String>,
+        /// Most steps to run at once, over the project's own. 0 for one per CPU core.
+        #[arg(short, long)]
+        job: Option<usize>,
+        /// Print every file that would be staged and every command that would be run, without running anything.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Browse past compile and package runs of the project
+    History {
+        /// Path to project.
+        #[arg(short, long)]
+        path: PathBuf,
+        /// Run to show in detail, by folder name or by number (0 for the latest).
+        run: Option<String>,
+        /// Print the log of every step of the run.
+        #[arg(long)]
+        log: bool,
+    },
+    /// Remove every folder the project's runs staged into, and optionally the packages they have built
+    Clean {
+        /// Path to project.
+        #[arg(short, long)]
+        path: PathBuf,
+        /// Also remove the package built by every package step.
+        #[arg(long)]
+        artifact: bool,
+    },
+    /// Build the project twice, then report every package that differs between both builds
+    CheckReproducible {
+        /// Path to project.
+        #[arg(short, long)]
+        path: PathBuf,
+        /// Profile to apply.
+        #[arg(long)]
+        profile: Option<String>,
+        /// Most steps to run at once, over the project's own. 0 for one per CPU core.
+        #[arg(long)]
+        job: Option<usize>,
+    },
+    /// Check every file in a folder against its SHA256SUMS or SHA512SUMS manifest
+    Verify {
+        /// Path to the folder with the manifest, e.g. the project's output folder.
+        #[arg(short, long)]
+        path: PathBuf,
+    },
+    /// Print what a built package holds, e.g. its control fields, scripts and files
+    Inspect {
+        /// Path to the package, either a Debian package or an AppImage.
+        path: PathBuf,
+        /// Print the report as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print the JSON Schema of the project file
+    Schema,
+    /// Manage the project file
+    Project {
+        #[command(subcommand)]
+        command: ProjectCommand,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum ProjectCommand {
+    /// Upgrade the project file to the current format version, keeping a backup
+    Migrate {
+        /// Path to project.
+        #[arg(short, long)]
+        path: PathBuf,
+    },
+    /// Rewrite the project file in another format, keeping a backup
+    Convert {
+        /// Path to project.
+        #[arg(short, long)]
+        path: PathBuf,
+        /// Format to convert to.
+        #[arg(long)]
+        to: Format,
     },
 }
 
@@ -92,29 +215,142 @@ impl Terminal {
 
         if let Some(command) = &terminal.command {
             match command {
-                Command::Compile { path } => todo!(),
-                Command::Package { path } => {
-                    let mut project = Project::load(path.to_path_buf())?;
+                Command::Compile {
+                    path,
+                    profile,
+                    job,
+                    dry_run,
+                } => {
+                    let mut project = Self::load(path, profile, job)?;
 
-                    // package project.
-                    project.package()?;
+                    if *dry_run {
+                        Self::preview(&project, false)?;
+                    } else {
+                        // compile project.
+                        project.compile()?;
+                        Self::wait(&mut project)?;
+                    }
+                }
+                Command::Package {
+                    path,
+                    profile,
+                    job,
+                    dry_run,
+                } => {
+                    let mut project = Self::load(path, profile, job)?;
 
-                    loop {
-                        for package in &mut project.package {
-                            package.poll_completion();
-                        }
+                    if *dry_run {
+                        Self::preview(&project, true)?;
+                    } else {
+                        // package project.
+                        project.package()?;
+                        Self::wait(&mut project)?;
+                    }
+                }
+                Command::CompileExport {
+                    path,
+                    profile,
+                    job,
+                    dry_run,
+                } => {
+                    let mut project = Self::load(path, profile, job)?;
 
-                        let complete = project
-                            .package
-                            .iter_mut()
-                            .all(|package| package.success_or_failure());
+                    if *dry_run {
+                        Self::preview(&project, false)?;
+                        Self::preview(&project, true)?;
+                    } else {
+                        // compile, then package project.
+                        project.compile()?;
+                        Self::wait(&mut project)?;
+                        project.package()?;
+                        Self::wait(&mut project)?;
+                    }
+                }
+                Command::History { path, run, log } => {
+                    let (path, _) = Project::locate(path.to_path_buf())?;
+
+                    if let Some(run) = run {
+                        Self::history_run(&Run::find(&path, run)?, *log)?;
+                    } else {
+                        let (list, warning) = Run::list(&path)?;
 
-                        if complete {
-                            break;
+                        for warning in warning {
+                            eprintln!("Warning: {warning}");
                         }
+
+                        Self::history_list(&list);
+                    }
+                }
+                Command::Clean { path, artifact } => {
+                    let mut project = Project::load(path.to_path_buf())?;
+
+                    for path in project.clean(*artifact)? {
+                        println!("Removed \"{}\".", path.display());
+                    }
+                }
+                Command::CheckReproducible { path, profile, job } => {
+                    let mut project = Self::load(path, profile, job)?;
+
+                    // the first build is kept aside, as the second one overwrites it.
+                    let keep = project
+                        .stage_folder(&project.meta_resolve()?)?
+                        .join("reproducible");
+                    std::fs::create_dir_all(&keep)?;
+
+                    let mut first = Vec::new();
+
+                    for (i, path) in Self::build(&mut project)?.into_iter().enumerate() {
+                        let copy = keep.join(i.to_string());
+                        std::fs::copy(&path, &copy)?;
+                        first.push((path, copy));
+                    }
+
+                    let second = Self::build(&mut project)?;
+
+                    Self::compare(&first, &second, &keep)?;
+                }
+                Command::Verify { path } => {
+                    let list = checksum::verify(path)?;
+                    let fail = list
+                        .iter()
+                        .filter(|(_, _, verdict)| *verdict != Verdict::Match)
+                        .count();
+
+                    for (file, hash, verdict) in &list {
+                        println!("{}: {verdict} ({})", file.display(), hash.file());
+                    }
+
+                    if fail > 0 {
+                        return Err(anyhow::Error::msg(format!(
+                            "Verify: {fail} of {} files failed their checksum.",
+                            list.len()
+                        )));
+                    }
+                }
+                Command::Inspect { path, json } => {
+                    let report = inspect::inspect(path)?;
+
+                    if *json {
+                        println!("{}", report.json()?);
+                    } else {
+                        print!("{report}");
                     }
                 }
-                Command::CompileExport { path } => todo!(),
+                Command::Schema => println!("{}", schema::schema()?),
+                Command::Project { command } => match command {
+                    ProjectCommand::Migrate { path } => {
+                        if Project::migrate(path.to_path_buf())? {
+                            println!("Migrated project file, original kept as a backup.");
+                        } else {
+                            println!("Project file is already up-to-date.");
+                        }
+                    }
+                    ProjectCommand::Convert { path, to } => {
+                        let file = Project::convert(path.to_path_buf(), *to)?;
+
+                        println!("Converted project file to \"{}\".", file.display());
+                    }
+                },
             }
 
             Ok(true)
@@ -122,4 +358,324 @@ impl Terminal {
             Ok(false)
         }
     }
+
+    /// print every run in a project's history, the latest first.
+    fn history_list(list: &[Run]) {
+        if list.is_empty() {
+            println!("No run yet.");
+            return;
+        }
+
+        println!(
+            "{:<4}{:<21}{:<10}{:<12}{:<10}{:<10}Folder",
+            "#", "Date", "Stage", "Profile", "Result", "Duration"
+        );
+
+        for (i, run) in list.iter().enumerate() {
+            println!(
+                "{:<4}{:<21}{:<10}{:<12}{:<10}{:<10}{}",
+                i,
+                run.date,
+                run.stage,
+                run.profile,
+                if run.success { "Success" } else { "Failure" },
+                format!("{:.1}s", run.duration),
+                run.name()
+            );
+        }
+    }
+
+    /// print a run in detail, with the log of every step if asked for.
+    fn history_run(run: &Run, log: bool) -> anyhow::Result<()> {
+        println!("Run:      {}", run.name());
+        println!("Date:     {}", run.date);
+        println!("Stage:    {}", run.stage);
+
+        if !run.profile.is_empty() {
+            println!("Profile:  {}", run.profile);
+        }
+
+        println!(
+            "Result:   {}",
+            if run.success { "Success" } else { "Failure" }
+        );
+        println!("Duration: {:.1}s", run.duration);
+        println!("Folder:   {}", run.path.display());
+        println!();
+        println!("Steps:");
+
+        for step in &run.step {
+            let mut line = if step.name.is_empty() {
+                format!("  {}: {}", step.kind, step.status)
+            } else {
+                format!("  {} ({}): {}", step.name, step.kind, step.status)
+            };
+
+            if let Some(code) = step.code {
+                line.push_str(&format!(", exit code {code}"));
+            }
+
+            if let Some(duration) = step.duration {
+                line.push_str(&format!(", {duration:.1}s"));
+            }
+
+            println!("{line}");
+
+            if let Some(path) = run.log_path(step) {
+                if log {
+                    for text in std::fs::read_to_string(path)?.lines() {
+                        println!("    | {text}");
+                    }
+                } else {
+                    println!("    log: {}", path.display());
+                }
+            }
+        }
+
+        if !run.artifact.is_empty() {
+            println!();
+            println!("Artifacts:");
+
+            for artifact in &run.artifact {
+                println!(
+                    "  {} ({}, {}): {}",
+                    artifact.step,
+                    artifact.kind,
+                    artifact.architecture,
+                    artifact.path.display()
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// print the progress, warnings and outcome of every step since the last report.
+    fn report(project: &mut Project, seen: &mut [Seen]) {
+        let list = project.compile.iter_mut().chain(project.package.iter_mut());
+
+        for (step, seen) in list.zip(seen.iter_mut()) {
+            let label = &seen.label;
+
+            for progress in &step.get_progress()[seen.progress..] {
+                if let Some(fraction) = progress.fraction {
+                    println!(
+                        "[{label}] {:>3}% {}",
+                        (fraction * 100.0).round(),
+                        progress.message
+                    );
+                } else {
+                    println!("[{label}] {}", progress.message);
+                }
+            }
+
+            for line in &step.get_log()[seen.log..] {
+                if line.stderr {
+                    eprintln!("{}", line.text);
+                } else {
+                    println!("{}", line.text);
+                }
+            }
+
+            for warning in &step.get_warning()[seen.warning..] {
+                eprintln!("[{label}] Warning: {warning}");
+            }
+
+            seen.progress = step.get_progress().len();
+            seen.log = step.get_log().len();
+            seen.warning = step.get_warning().len();
+
+            if !seen.done && step.success_or_failure() {
+                seen.done = true;
+
+                match step.get_status() {
+                    ExportStatus::Failure(_) => println!("[{label}] Failure"),
+                    status => println!("[{label}] {status}"),
+                }
+            }
+        }
+    }
+
+    extern "C" fn interrupt(_: libc::c_int) {
+        INTERRUPT.store(true, Ordering::SeqCst);
+    }
+
+    /// load a project, with an optional profile active and job limit.
+    fn load(path: &Path, profile: &Option<String>, job: &Option<usize>) -> anyhow::Result<Project> {
+        let mut project = Project::load(path.to_path_buf())?;
+
+        if let Some(job) = job {
+            project.job = *job;
+        }
+
+        if let Some(profile) = profile {
+            project.profile_active = profile.clone();
+            project.profile_get()?;
+        }
+
+        Ok(project)
+    }
+
+    /// print everything the enabled steps of a stage would do, without running them.
+    fn preview(project: &Project, package: bool) -> anyhow::Result<()> {
+        println!("{}:", if package { "Package" } else { "Compile" });
+
+        for plan in project.preview(package)? {
+            if plan.step.is_empty() {
+                println!("\n[{}]", plan.kind);
+            } else {
+                println!("\n[{}] ({})", plan.step, plan.kind);
+            }
+
+            print!("{plan}");
+        }
+
+        println!();
+
+        Ok(())
+    }
+
+    /// compile, then package the project, returning every package built.
+    fn build(project: &mut Project) -> anyhow::Result<Vec<PathBuf>> {
+        project.compile()?;
+        Self::wait(project)?;
+        project.package()?;
+        Self::wait(project)?;
+
+        Ok(project
+            .artifact
+            .iter()
+            .filter(|x| x.kind == ArtifactKind::Package)
+            .map(|x| x.path.clone())
+            .collect())
+    }
+
+    /// compare the packages of two builds, as (package, copy of its first build) and package,
+    /// failing if any differs. the copies are removed, unless a package differs.
+    fn compare(
+        first: &[(PathBuf, PathBuf)],
+        second: &[PathBuf],
+        keep: &Path,
+    ) -> anyhow::Result<()> {
+        let mut differ = 0;
+
+        for path in second {
+            let Some((_, copy)) = first.iter().find(|(x, _)| x == path) else {
+                println!("New: {}", path.display());
+                differ += 1;
+                continue;
+            };
+
+            if Hash::Sha256.hash(copy)? == Hash::Sha256.hash(path)? {
+                println!("Same: {}", path.display());
+                continue;
+            }
+
+            differ += 1;
+
+            let a = std::fs::read(copy)?;
+            let b = std::fs::read(path)?;
+            let offset = a
+                .iter()
+                .zip(&b)
+                .position(|(a, b)| a != b)
+                .unwrap_or(a.len().min(b.len()));
+
+            println!(
+                "Differs: {} ({} and {} bytes, first difference at byte {offset}, first build kept as \"{}\")",
+                path.display(),
+                a.len(),
+                b.len(),
+                copy.display()
+            );
+        }
+
+        for (path, _) in first.iter().filter(|(x, _)| !second.contains(x)) {
+            println!("Missing: {}", path.display());
+            differ += 1;
+        }
+
+        if differ > 0 {
+            return Err(anyhow::Error::msg(format!(
+                "Reproducible: {differ} of {} packages differ between two builds.",
+                second.len()
+            )));
+        }
+
+        std::fs::remove_dir_all(keep)?;
+
+        Ok(())
+    }
+
+    /// wait for every step to finish, failing if any step has failed, was cancelled or timed out.
+    /// an interrupt (Ctrl+C) cancels every step still running.
+    fn wait(project: &mut Project) -> anyhow::Result<()> {
+        // steps run in their own process group, out of reach of the terminal's interrupt.
+        unsafe {
+            libc::signal(
+                libc::SIGINT,
+                Self::interrupt as *const () as libc::sighandler_t,
+            );
+        }
+
+        let mut seen: Vec<Seen> = project
+            .compile
+            .iter_mut()
+            .chain(project.package.iter_mut())
+            .map(|step| Seen {
+                label: label(step.as_ref()),
+                progress: step.get_progress().len(),
+                log: step.get_log().len(),
+                warning: step.get_warning().len(),
+                done: step.success_or_failure(),
+            })
+            .collect();
+
+        loop {
+            let done = project.poll()?;
+
+            Self::report(project, &mut seen);
+
+            if done {
+                break;
+            }
+
+            if INTERRUPT.swap(false, Ordering::SeqCst) {
+                project.cancel();
+            }
+
+            std::thread::sleep(Duration::from_millis(50));
+        }
+
+        let mut failure = Vec::new();
+
+        for (step, seen) in project
+            .compile
+            .iter_mut()
+            .chain(project.package.iter_mut())
+            .zip(&seen)
+        {
+            let label = &seen.label;
+
+            match step.get_status() {
+                // every line of a multi-line error, e.g. a command's stderr, is told apart.
+                ExportStatus::Failure(error) => failure.extend(
+                    error
+                        .trim_end()
+                        .lines()
+                        .map(|line| format!("[{label}] {line}")),
+                ),
+                status @ (ExportStatus::Cancelled | ExportStatus::TimedOut) => {
+                    failure.push(format!("[{label}] {status}."))
+                }
+                _ => {}
+            }
+        }
+
+        if failure.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow::Error::msg(failure.join("\n")))
+        }
+    }
 }